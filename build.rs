@@ -149,6 +149,12 @@ fn main() {
         conf.define("SUPPORT_CUSTOM_FRAME_CONTROL", "ON");
     }
 
+    // Build miniaudio with only the null backend, so audio works without a sound device (e.g. CI)
+    if cfg!(feature = "null_audio") {
+        conf.cflag("-DMA_ENABLE_ONLY_SPECIFIC_BACKENDS")
+            .cflag("-DMA_ENABLE_NULL");
+    }
+
     // Enable wayland cmake flag if feature is specified
     if cfg!(feature = "wayland") {
         conf.define("USE_WAYLAND", "ON");
@@ -1546,7 +1546,7 @@ impl ComputeSHA1Handle {
 
 // Automation events functionality
 
-/// Load automation events list from file, NULL for empty list, capacity = MAX_AUTOMATION_EVENTS
+/// Load automation events list from file, capacity = MAX_AUTOMATION_EVENTS
 #[inline]
 pub unsafe fn load_automation_event_list(
     file_name: &CStr,
@@ -1558,6 +1558,16 @@ pub unsafe fn load_automation_event_list(
     }
 }
 
+/// Create an empty automation events list, capacity = MAX_AUTOMATION_EVENTS
+#[inline]
+pub unsafe fn new_automation_event_list() -> sys::AutomationEventList {
+    unsafe {
+        sys::LoadAutomationEventList(
+            null(),
+        )
+    }
+}
+
 /// Unload automation events list from file
 #[inline]
 pub unsafe fn unload_automation_event_list(
@@ -915,13 +915,13 @@ pub unsafe fn rl_disable_framebuffer() {
 //     }
 // }
 
-// /// Get default shader id
-// #[inline]
-// pub unsafe fn rlGetShaderIdDefault() -> u32 {
-//     unsafe {
-//         sys::
-//     }
-// }
+/// Get default shader id
+#[inline]
+pub unsafe fn rl_get_shader_id_default() -> u32 {
+    unsafe {
+        sys::rlGetShaderIdDefault()
+    }
+}
 
 // /// Get default shader locations
 // #[inline]
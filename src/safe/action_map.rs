@@ -0,0 +1,640 @@
+//! Game actions bound to rebindable inputs
+//!
+//! An [`ActionMap`] binds actions to keys, mouse buttons and gamepad inputs, and is evaluated once per frame:
+//!
+//! ```ignore
+//! #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+//! enum Action { Jump, Move, Save }
+//!
+//! let mut actions = ActionMap::new();
+//! actions.bind(Action::Jump, InputSource::Key(KeyboardKey::Space));
+//! actions.bind(Action::Jump, InputSource::GamepadButton(GamepadButton::RightFaceDown));
+//! actions.bind(Action::Move, Binding::wasd());
+//! actions.bind(Action::Move, Binding::Stick { x: GamepadAxis::LeftX, y: GamepadAxis::LeftY });
+//! actions.bind(Action::Save, Binding::Chord(vec![InputSource::Key(KeyboardKey::LeftControl), InputSource::Key(KeyboardKey::S)]));
+//!
+//! while !rl.should_close() {
+//!     actions.update(&rl);
+//!     if actions.is_pressed(Action::Jump) { /* ... */ }
+//!     let movement = actions.vector(Action::Move);
+//!     // ...
+//! }
+//! ```
+//!
+//! Bindings convert to and from a line-based text format, so players can rebind controls:
+//!
+//! ```text
+//! Jump = Space
+//! Jump = Gamepad.RightFaceDown
+//! Move = vector(W, S, A, D)
+//! Move = stick(GamepadAxis.LeftX, GamepadAxis.LeftY)
+//! Save = LeftControl+S
+//! Throttle = axis(-GamepadAxis.LeftY, GamepadAxis.LeftY)
+//! ```
+//!
+//! Each line binds one action, so an action with several bindings has several lines.
+//! Inputs are written as their [`Display`](fmt::Display) names, such as `LeftShift`, `Mouse.Left`,
+//! `Gamepad.RightTrigger2` and `GamepadAxis.LeftX`; a leading `-` selects the negative half of an axis.
+
+use std::{collections::HashMap, fmt, hash::Hash, str::FromStr};
+use super::*;
+
+/// Default dead zone, see [`ActionMap::with_dead_zone()`]
+const DEFAULT_DEAD_ZONE: f32 = 0.2;
+
+/// Half of a gamepad axis
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AxisDirection {
+    /// Right or down on a stick, pressed on a trigger
+    Positive,
+    /// Left or up on a stick
+    Negative,
+}
+
+/// A single physical input
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum InputSource {
+    /// A keyboard key
+    Key(KeyboardKey),
+    /// A mouse button
+    Mouse(MouseButton),
+    /// A button on the [`ActionMap`]'s gamepad
+    GamepadButton(GamepadButton),
+    /// Half of an axis on the [`ActionMap`]'s gamepad
+    GamepadAxis(GamepadAxis, AxisDirection),
+}
+
+/// How an action is triggered
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Binding {
+    /// Every input held at once, such as Ctrl+S; a single input is a chord of one
+    Chord(Vec<InputSource>),
+    /// One dimension from two inputs, `-1.0..=1.0`
+    Axis {
+        /// Input pulling towards `-1.0`
+        negative: InputSource,
+        /// Input pulling towards `1.0`
+        positive: InputSource,
+    },
+    /// Two dimensions from four inputs, such as WASD, with y pointing down
+    Vector {
+        /// Input pulling towards negative y
+        up: InputSource,
+        /// Input pulling towards positive y
+        down: InputSource,
+        /// Input pulling towards negative x
+        left: InputSource,
+        /// Input pulling towards positive x
+        right: InputSource,
+    },
+    /// Two axes of a stick on the [`ActionMap`]'s gamepad, with a radial dead zone
+    Stick {
+        /// Horizontal axis
+        x: GamepadAxis,
+        /// Vertical axis
+        y: GamepadAxis,
+    },
+}
+
+impl From<InputSource> for Binding {
+    #[inline]
+    fn from(source: InputSource) -> Self {
+        Self::Chord(vec![source])
+    }
+}
+
+impl Binding {
+    /// W, S, A and D as a [`Binding::Vector`]
+    #[inline]
+    pub const fn wasd() -> Self {
+        Self::Vector {
+            up: InputSource::Key(KeyboardKey::W),
+            down: InputSource::Key(KeyboardKey::S),
+            left: InputSource::Key(KeyboardKey::A),
+            right: InputSource::Key(KeyboardKey::D),
+        }
+    }
+
+    /// The arrow keys as a [`Binding::Vector`]
+    #[inline]
+    pub const fn arrows() -> Self {
+        Self::Vector {
+            up: InputSource::Key(KeyboardKey::Up),
+            down: InputSource::Key(KeyboardKey::Down),
+            left: InputSource::Key(KeyboardKey::Left),
+            right: InputSource::Key(KeyboardKey::Right),
+        }
+    }
+
+    /// Current value, zero when inactive
+    ///
+    /// Buttons and chords have `x` set to `1.0` while active, axes only use `x`.
+    fn value(&self, input: &impl ReadInput, gamepad: usize, dead_zone: f32) -> Vector2 {
+        let source = |source: &InputSource| source.value(input, gamepad, dead_zone);
+        match self {
+            Self::Chord(sources) => Vector2 {
+                x: sources.iter().map(source).reduce(f32::min).unwrap_or(0.0),
+                y: 0.0,
+            },
+            Self::Axis { negative, positive } => Vector2 {
+                x: source(positive) - source(negative),
+                y: 0.0,
+            },
+            Self::Vector { up, down, left, right } => {
+                let value = Vector2 {
+                    x: source(right) - source(left),
+                    y: source(down) - source(up),
+                };
+                // Diagonals shouldn't be faster
+                let length = length(value);
+                if length > 1.0 {
+                    Vector2 { x: value.x / length, y: value.y / length }
+                } else {
+                    value
+                }
+            }
+            &Self::Stick { x, y } => {
+                if !input.is_gamepad_available(gamepad) {
+                    return Vector2::default();
+                }
+                let value = Vector2 {
+                    x: input.get_gamepad_axis_movement(gamepad, x),
+                    y: input.get_gamepad_axis_movement(gamepad, y),
+                };
+                let length = length(value);
+                if length <= dead_zone {
+                    Vector2::default()
+                } else {
+                    let scale = apply_dead_zone(length, dead_zone) / length;
+                    Vector2 { x: value.x * scale, y: value.y * scale }
+                }
+            }
+        }
+    }
+}
+
+impl InputSource {
+    /// Current value in `0.0..=1.0`
+    ///
+    /// Triggers rest at `-1.0`, so their positive half covers their whole travel.
+    fn value(self, input: &impl ReadInput, gamepad: usize, dead_zone: f32) -> f32 {
+        match self {
+            Self::Key(key) => f32::from(input.is_key_down(key)),
+            Self::Mouse(button) => f32::from(input.is_mouse_button_down(button)),
+            Self::GamepadButton(button) => f32::from(input.is_gamepad_button_down(gamepad, button)),
+            Self::GamepadAxis(axis, direction) => {
+                if !input.is_gamepad_available(gamepad) {
+                    return 0.0;
+                }
+                let movement = input.get_gamepad_axis_movement(gamepad, axis);
+                let value = match (axis, direction) {
+                    (GamepadAxis::LeftTrigger | GamepadAxis::RightTrigger, AxisDirection::Positive) => (movement + 1.0) / 2.0,
+                    (GamepadAxis::LeftTrigger | GamepadAxis::RightTrigger, AxisDirection::Negative) => 0.0,
+                    (_, AxisDirection::Positive) => movement,
+                    (_, AxisDirection::Negative) => -movement,
+                };
+                apply_dead_zone(value, dead_zone)
+            }
+        }
+    }
+}
+
+/// Rescale `value` so that `dead_zone..=1.0` maps to `0.0..=1.0`, and anything below to `0.0`
+fn apply_dead_zone(value: f32, dead_zone: f32) -> f32 {
+    if value <= dead_zone {
+        0.0
+    } else {
+        ((value - dead_zone) / (1.0 - dead_zone)).min(1.0)
+    }
+}
+
+#[inline]
+fn length(value: Vector2) -> f32 {
+    value.x.hypot(value.y)
+}
+
+/// Input queries needed to evaluate bindings
+trait ReadInput {
+    fn is_key_down(&self, key: KeyboardKey) -> bool;
+    fn is_mouse_button_down(&self, button: MouseButton) -> bool;
+    fn is_gamepad_available(&self, gamepad: usize) -> bool;
+    fn is_gamepad_button_down(&self, gamepad: usize, button: GamepadButton) -> bool;
+    fn get_gamepad_axis_movement(&self, gamepad: usize, axis: GamepadAxis) -> f32;
+}
+
+impl ReadInput for WindowInner<'_> {
+    #[inline]
+    fn is_key_down(&self, key: KeyboardKey) -> bool {
+        WindowInner::is_key_down(self, key)
+    }
+
+    #[inline]
+    fn is_mouse_button_down(&self, button: MouseButton) -> bool {
+        WindowInner::is_mouse_button_down(self, button)
+    }
+
+    #[inline]
+    fn is_gamepad_available(&self, gamepad: usize) -> bool {
+        WindowInner::is_gamepad_available(self, gamepad)
+    }
+
+    #[inline]
+    fn is_gamepad_button_down(&self, gamepad: usize, button: GamepadButton) -> bool {
+        WindowInner::is_gamepad_button_down(self, gamepad, button)
+    }
+
+    #[inline]
+    fn get_gamepad_axis_movement(&self, gamepad: usize, axis: GamepadAxis) -> f32 {
+        WindowInner::get_gamepad_axis_movement(self, gamepad, axis)
+    }
+}
+
+impl ReadInput for InputState {
+    #[inline]
+    fn is_key_down(&self, key: KeyboardKey) -> bool {
+        InputState::is_key_down(self, key)
+    }
+
+    #[inline]
+    fn is_mouse_button_down(&self, button: MouseButton) -> bool {
+        InputState::is_mouse_button_down(self, button)
+    }
+
+    #[inline]
+    fn is_gamepad_available(&self, gamepad: usize) -> bool {
+        InputState::is_gamepad_available(self, gamepad)
+    }
+
+    #[inline]
+    fn is_gamepad_button_down(&self, gamepad: usize, button: GamepadButton) -> bool {
+        InputState::is_gamepad_button_down(self, gamepad, button)
+    }
+
+    #[inline]
+    fn get_gamepad_axis_movement(&self, gamepad: usize, axis: GamepadAxis) -> f32 {
+        InputState::get_gamepad_axis_movement(self, gamepad, axis)
+    }
+}
+
+/// State of an action for the current frame
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct ActionState {
+    /// Value of the strongest active binding, zero when inactive
+    pub value: Vector2,
+    /// Whether any binding is active
+    pub down: bool,
+    /// Whether the action became active this frame
+    pub pressed: bool,
+    /// Whether the action became inactive this frame
+    pub released: bool,
+}
+
+/// Actions bound to inputs, evaluated once per frame
+#[derive(Debug, Clone)]
+pub struct ActionMap<A> {
+    bindings: Vec<(A, Binding)>,
+    states: HashMap<A, ActionState>,
+    gamepad: usize,
+    dead_zone: f32,
+}
+
+impl<A: Copy + Eq + Hash> Default for ActionMap<A> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<A: Copy + Eq + Hash> ActionMap<A> {
+    /// No bindings, reading gamepad 0 with a dead zone of `0.2`
+    #[inline]
+    pub fn new() -> Self {
+        Self {
+            bindings: Vec::new(),
+            states: HashMap::new(),
+            gamepad: 0,
+            dead_zone: DEFAULT_DEAD_ZONE,
+        }
+    }
+
+    /// Set the gamepad that gamepad inputs are read from
+    #[inline]
+    pub fn with_gamepad(mut self, gamepad: usize) -> Self {
+        self.gamepad = gamepad;
+        self
+    }
+
+    /// Set how far an axis must move before it counts, in `0.0..1.0`
+    ///
+    /// Movement past the dead zone is rescaled, so values still start at `0.0` and reach `1.0`.
+    #[inline]
+    pub fn with_dead_zone(mut self, dead_zone: f32) -> Self {
+        self.dead_zone = dead_zone;
+        self
+    }
+
+    /// Gamepad that gamepad inputs are read from
+    #[inline]
+    pub const fn gamepad(&self) -> usize {
+        self.gamepad
+    }
+
+    /// Set the gamepad that gamepad inputs are read from
+    #[inline]
+    pub fn set_gamepad(&mut self, gamepad: usize) {
+        self.gamepad = gamepad;
+    }
+
+    /// How far an axis must move before it counts
+    #[inline]
+    pub const fn dead_zone(&self) -> f32 {
+        self.dead_zone
+    }
+
+    /// Set how far an axis must move before it counts
+    #[inline]
+    pub fn set_dead_zone(&mut self, dead_zone: f32) {
+        self.dead_zone = dead_zone;
+    }
+
+    /// Add a binding to an action, keeping its existing bindings
+    #[inline]
+    pub fn bind(&mut self, action: A, binding: impl Into<Binding>) {
+        self.bindings.push((action, binding.into()));
+    }
+
+    /// Remove every binding of an action
+    #[inline]
+    pub fn unbind(&mut self, action: A) {
+        self.bindings.retain(|(bound, _)| *bound != action);
+    }
+
+    /// Replace every binding of an action with `binding`
+    #[inline]
+    pub fn rebind(&mut self, action: A, binding: impl Into<Binding>) {
+        self.unbind(action);
+        self.bind(action, binding);
+    }
+
+    /// Bindings of an action, in the order they were added
+    #[inline]
+    pub fn bindings(&self, action: A) -> impl Iterator<Item = &Binding> {
+        self.bindings.iter()
+            .filter(move |(bound, _)| *bound == action)
+            .map(|(_, binding)| binding)
+    }
+
+    /// Every binding, in the order they were added
+    #[inline]
+    pub fn all_bindings(&self) -> &[(A, Binding)] {
+        &self.bindings
+    }
+
+    /// Evaluate every action against the window's input; call once per frame
+    #[inline]
+    pub fn update(&mut self, rl: &WindowInner<'_>) {
+        self.evaluate(rl);
+    }
+
+    /// Evaluate every action against an input snapshot; call once per frame
+    #[inline]
+    pub fn update_from(&mut self, input: &InputState) {
+        self.evaluate(input);
+    }
+
+    fn evaluate(&mut self, input: &impl ReadInput) {
+        for state in self.states.values_mut() {
+            state.value = Vector2::default();
+        }
+        for (action, binding) in &self.bindings {
+            let value = binding.value(input, self.gamepad, self.dead_zone);
+            let state = self.states.entry(*action).or_default();
+            if length(value) > length(state.value) {
+                state.value = value;
+            }
+        }
+        for state in self.states.values_mut() {
+            let was_down = state.down;
+            state.down = state.value != Vector2::default();
+            state.pressed = state.down && !was_down;
+            state.released = !state.down && was_down;
+        }
+    }
+
+    /// State of an action, inactive if it has never been bound
+    #[inline]
+    pub fn state(&self, action: A) -> ActionState {
+        self.states.get(&action).copied().unwrap_or_default()
+    }
+
+    /// Check if an action is active
+    #[inline]
+    pub fn is_down(&self, action: A) -> bool {
+        self.state(action).down
+    }
+
+    /// Check if an action became active this frame
+    #[inline]
+    pub fn is_pressed(&self, action: A) -> bool {
+        self.state(action).pressed
+    }
+
+    /// Check if an action became inactive this frame
+    #[inline]
+    pub fn is_released(&self, action: A) -> bool {
+        self.state(action).released
+    }
+
+    /// One-dimensional value of an action, `0.0..=1.0` for buttons and `-1.0..=1.0` for axes
+    #[inline]
+    pub fn value(&self, action: A) -> f32 {
+        self.state(action).value.x
+    }
+
+    /// Two-dimensional value of an action, for [`Binding::Vector`] and [`Binding::Stick`]
+    #[inline]
+    pub fn vector(&self, action: A) -> Vector2 {
+        self.state(action).value
+    }
+}
+
+impl fmt::Display for InputSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Key(key) => write!(f, "{key}"),
+            Self::Mouse(button) => write!(f, "{button}"),
+            Self::GamepadButton(button) => write!(f, "{button}"),
+            Self::GamepadAxis(axis, AxisDirection::Positive) => write!(f, "{axis}"),
+            Self::GamepadAxis(axis, AxisDirection::Negative) => write!(f, "-{axis}"),
+        }
+    }
+}
+
+/// An error from parsing an [`InputSource`] or a [`Binding`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BindingParseError {
+    /// The binding is empty
+    Empty,
+    /// An input name is not recognized
+    UnknownInput(String),
+    /// A `name(...)` binding is not one of `axis`, `vector` or `stick`
+    UnknownFunction(String),
+    /// A `name(...)` binding has the wrong number or kind of arguments
+    InvalidArguments(String),
+}
+
+impl fmt::Display for BindingParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Empty => write!(f, "empty binding"),
+            Self::UnknownInput(name) => write!(f, "unknown input `{name}`"),
+            Self::UnknownFunction(name) => write!(f, "unknown binding `{name}(...)`"),
+            Self::InvalidArguments(name) => write!(f, "invalid arguments for `{name}(...)`"),
+        }
+    }
+}
+
+impl std::error::Error for BindingParseError {}
+
+impl FromStr for InputSource {
+    type Err = BindingParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let unknown = || BindingParseError::UnknownInput(s.to_owned());
+        if s.is_empty() {
+            return Err(BindingParseError::Empty);
+        }
+        if let Some(axis) = s.strip_prefix('-') {
+            return axis.parse()
+                .map(|axis| Self::GamepadAxis(axis, AxisDirection::Negative))
+                .map_err(|_| unknown());
+        }
+        // Prefixes keep the names of different kinds of input apart, see `sys_enum_wrapper!`
+        s.parse().map(|axis| Self::GamepadAxis(axis, AxisDirection::Positive))
+            .or_else(|_| s.parse().map(Self::GamepadButton))
+            .or_else(|_| s.parse().map(Self::Mouse))
+            .or_else(|_| s.parse().map(Self::Key))
+            .map_err(|_| unknown())
+    }
+}
+
+impl fmt::Display for Binding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Chord(sources) => {
+                for (i, source) in sources.iter().enumerate() {
+                    if i > 0 {
+                        f.write_str("+")?;
+                    }
+                    write!(f, "{source}")?;
+                }
+                Ok(())
+            }
+            Self::Axis { negative, positive } => write!(f, "axis({negative}, {positive})"),
+            Self::Vector { up, down, left, right } => write!(f, "vector({up}, {down}, {left}, {right})"),
+            Self::Stick { x, y } => write!(f, "stick({x}, {y})"),
+        }
+    }
+}
+
+impl FromStr for Binding {
+    type Err = BindingParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let Some((name, args)) = s.strip_suffix(')').and_then(|s| s.split_once('(')) else {
+            return s.split('+')
+                .map(str::parse)
+                .collect::<Result<Vec<InputSource>, _>>()
+                .map(Self::Chord);
+        };
+        let name = name.trim();
+        let invalid = || BindingParseError::InvalidArguments(name.to_owned());
+        let args = args.split(',')
+            .map(str::parse)
+            .collect::<Result<Vec<InputSource>, _>>()?;
+        match (name, args.as_slice()) {
+            ("axis", &[negative, positive]) => Ok(Self::Axis { negative, positive }),
+            ("vector", &[up, down, left, right]) => Ok(Self::Vector { up, down, left, right }),
+            ("stick", &[x, y]) => match (x, y) {
+                (
+                    InputSource::GamepadAxis(x, AxisDirection::Positive),
+                    InputSource::GamepadAxis(y, AxisDirection::Positive),
+                ) => Ok(Self::Stick { x, y }),
+                _ => Err(invalid()),
+            },
+            ("axis" | "vector" | "stick", _) => Err(invalid()),
+            _ => Err(BindingParseError::UnknownFunction(name.to_owned())),
+        }
+    }
+}
+
+/// An error from parsing the bindings of an [`ActionMap`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ActionMapParseError {
+    /// A line is not an `action = binding` pair
+    MalformedLine {
+        /// 1-based line number
+        line: usize,
+    },
+    /// An action name could not be parsed
+    InvalidAction {
+        /// 1-based line number
+        line: usize,
+    },
+    /// A binding could not be parsed
+    InvalidBinding {
+        /// 1-based line number
+        line: usize,
+        /// Why the binding is invalid
+        error: BindingParseError,
+    },
+}
+
+impl fmt::Display for ActionMapParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MalformedLine { line } => write!(f, "line {line}: expected `action = binding`"),
+            Self::InvalidAction { line } => write!(f, "line {line}: unknown action"),
+            Self::InvalidBinding { line, error } => write!(f, "line {line}: {error}"),
+        }
+    }
+}
+
+impl std::error::Error for ActionMapParseError {}
+
+impl<A: fmt::Display> fmt::Display for ActionMap<A> {
+    /// Write the bindings, one `action = binding` line each
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (action, binding) in &self.bindings {
+            writeln!(f, "{action} = {binding}")?;
+        }
+        Ok(())
+    }
+}
+
+impl<A: Copy + Eq + Hash + FromStr> ActionMap<A> {
+    /// Replace every binding with ones parsed from the text format, see the [module docs](crate::safe::action_map)
+    ///
+    /// Blank lines and lines starting with `#` are skipped. The bindings are left unchanged on error.
+    pub fn load_bindings(&mut self, s: &str) -> Result<(), ActionMapParseError> {
+        let mut bindings = Vec::new();
+        for (index, line) in s.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let number = index + 1;
+            let (action, binding) = line.split_once('=')
+                .ok_or(ActionMapParseError::MalformedLine { line: number })?;
+            let action = action.trim().parse::<A>()
+                .map_err(|_| ActionMapParseError::InvalidAction { line: number })?;
+            let binding = binding.parse::<Binding>()
+                .map_err(|error| ActionMapParseError::InvalidBinding { line: number, error })?;
+            bindings.push((action, binding));
+        }
+        self.bindings = bindings;
+        Ok(())
+    }
+}
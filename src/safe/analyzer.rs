@@ -0,0 +1,395 @@
+//! Spectrum and level analysis of playing audio, for visualizers
+//!
+//! An [`AudioAnalyzer`] taps a stream or the master mix with an audio processor that only copies
+//! samples into a [lock-free ring buffer](super::ring_buffer). All analysis happens on the game thread
+//! in [`AudioAnalyzer::update()`].
+
+use std::{ffi::{c_uint, c_void}, f32::consts::PI, ptr, sync::atomic::{AtomicPtr, Ordering}, time::Duration};
+use super::*;
+
+/// One stereo frame, as handed to audio processors
+type Frame = [f32; 2];
+
+/// Number of taps that may be attached at the same time
+const TAP_SLOTS: usize = 8;
+
+/// Producer owned by each attached tap, null when the slot is free
+static TAPS: [AtomicPtr<RingProducer<Frame>>; TAP_SLOTS] = [const { AtomicPtr::new(ptr::null_mut()) }; TAP_SLOTS];
+
+/// Raylib processors carry no user data, so every slot gets its own function
+const TAP_PROCESSORS: [unsafe extern "C" fn(*mut c_void, c_uint); TAP_SLOTS] = [
+    tap_processor::<0>,
+    tap_processor::<1>,
+    tap_processor::<2>,
+    tap_processor::<3>,
+    tap_processor::<4>,
+    tap_processor::<5>,
+    tap_processor::<6>,
+    tap_processor::<7>,
+];
+
+/// Processor that copies every frame into the ring buffer of slot `SLOT`
+unsafe extern "C" fn tap_processor<const SLOT: usize>(buffer: *mut c_void, frames: c_uint) {
+    let producer = TAPS[SLOT].load(Ordering::Acquire);
+    if producer.is_null() {
+        return;
+    }
+    // SAFETY: Raylib passes `frames` stereo frames of 32-bit float samples
+    let frames = unsafe { std::slice::from_raw_parts(buffer.cast::<Frame>().cast_const(), frames as usize) };
+    // SAFETY:
+    // - Only this processor dereferences the slot's producer while it is attached
+    // - Raylib runs processors on the audio thread while holding the audio lock, and
+    //   `AudioTap` detaches the processor (taking the same lock) before freeing the producer
+    let producer = unsafe { &mut *producer };
+    producer.push_slice(frames);
+}
+
+/// A processor attached to a stream or the master mix, feeding a ring buffer
+///
+/// Detaches the processor and frees its slot when dropped
+struct AudioTap<'a> {
+    slot: usize,
+    /// [`None`] for the master mix
+    stream: Option<sys::AudioStream>,
+    _device: PhantomData<&'a AudioDevice>,
+}
+
+impl Drop for AudioTap<'_> {
+    fn drop(&mut self) {
+        let processor = Some(TAP_PROCESSORS[self.slot]);
+        // SAFETY:
+        // - `AudioTap` borrows the device (and the stream, if any), so both are still loaded
+        // - Raylib holds the audio lock while detaching, so the processor is not running once this returns
+        unsafe {
+            match self.stream {
+                Some(stream) => low::detach_audio_stream_processor(stream, processor),
+                None => low::detach_audio_mixed_processor(processor),
+            }
+        }
+        let producer = TAPS[self.slot].swap(ptr::null_mut(), Ordering::AcqRel);
+        // SAFETY: The pointer was created by `Box::into_raw` in `AudioTap::attach` and the processor is detached
+        drop(unsafe { Box::from_raw(producer) });
+    }
+}
+
+impl AudioTap<'_> {
+    /// Claim a free slot and attach its processor
+    ///
+    /// # Safety
+    ///
+    /// The audio device must be initialized and `stream` (if any) must be loaded for the lifetime of the tap.
+    unsafe fn attach(stream: Option<sys::AudioStream>, capacity: usize) -> Option<(Self, RingConsumer<Frame>)> {
+        let (producer, consumer) = ring_buffer(capacity);
+        let producer = Box::into_raw(Box::new(producer));
+        let Some(slot) = TAPS.iter()
+            .position(|slot| slot.compare_exchange(ptr::null_mut(), producer, Ordering::AcqRel, Ordering::Acquire).is_ok())
+        else {
+            // SAFETY: Created above and never shared
+            drop(unsafe { Box::from_raw(producer) });
+            return None;
+        };
+        let processor = Some(TAP_PROCESSORS[slot]);
+        // SAFETY: Upheld by caller; the slot is claimed, so this processor is attached only once
+        unsafe {
+            match stream {
+                Some(stream) => low::attach_audio_stream_processor(stream, processor),
+                None => low::attach_audio_mixed_processor(processor),
+            }
+        }
+        Some((Self { slot, stream, _device: PhantomData }, consumer))
+    }
+}
+
+/// Settings for an [`AudioAnalyzer`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AnalyzerConfig {
+    /// Number of samples in each FFT window
+    ///
+    /// Clamped to `32..=65536` and rounded up to a power of two.
+    /// The spectrum is recomputed every half window.
+    pub fft_size: usize,
+    /// Sample rate of the tapped audio, used to convert bins to frequencies
+    ///
+    /// Processors receive audio at the device's sample rate, not the stream's.
+    pub sample_rate: u32,
+    /// Number of stereo frames the ring buffer between the audio thread and the analyzer can hold
+    pub buffer_frames: usize,
+    /// How many times its recent average the spectral flux must reach to count as an onset
+    pub onset_threshold: f32,
+    /// Minimum spectral flux for an onset, so noise in near silence is ignored
+    pub onset_floor: f32,
+    /// Minimum time between two onsets
+    pub onset_cooldown: Duration,
+}
+
+impl Default for AnalyzerConfig {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            fft_size: 1024,
+            sample_rate: 48000,
+            buffer_frames: 16384,
+            onset_threshold: 1.5,
+            onset_floor: 0.05,
+            onset_cooldown: Duration::from_millis(100),
+        }
+    }
+}
+
+/// Peak and RMS level of the most recent analysis hop, across both channels
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct AudioLevels {
+    /// Largest absolute sample value
+    pub peak: f32,
+    /// Root mean square of the samples
+    pub rms: f32,
+}
+
+/// Number of past spectral flux values averaged for the onset threshold
+const FLUX_HISTORY: usize = 16;
+
+/// Windowed FFT, level meter and onset detector for playing audio
+///
+/// Call [`AudioAnalyzer::update()`] once per frame, then read the results.
+pub struct AudioAnalyzer<'a> {
+    tap: Option<(AudioTap<'a>, RingConsumer<Frame>)>,
+    config: AnalyzerConfig,
+    fft_size: usize,
+    /// Mono samples, oldest at `history_pos`
+    history: Vec<f32>,
+    history_pos: usize,
+    /// Frames pushed since the last analysis
+    hop_frames: usize,
+    hop_peak: f32,
+    hop_sum_sq: f32,
+    window: Vec<f32>,
+    twiddles: Vec<(f32, f32)>,
+    re: Vec<f32>,
+    im: Vec<f32>,
+    spectrum: Vec<f32>,
+    prev_spectrum: Vec<f32>,
+    levels: AudioLevels,
+    flux: [f32; FLUX_HISTORY],
+    flux_pos: usize,
+    /// Frames left until another onset may be reported
+    cooldown_frames: usize,
+    onsets: u32,
+}
+
+impl<'a> AudioAnalyzer<'a> {
+    /// Create an analyzer that is not attached to any audio, see [`AudioAnalyzer::push_frames()`]
+    pub fn new(config: AnalyzerConfig) -> Self {
+        let fft_size = config.fft_size.clamp(32, 65536).next_power_of_two();
+        Self {
+            tap: None,
+            config,
+            fft_size,
+            history: vec![0.0; fft_size],
+            history_pos: 0,
+            hop_frames: 0,
+            hop_peak: 0.0,
+            hop_sum_sq: 0.0,
+            // Periodic Hann window
+            window: (0..fft_size)
+                .map(|i| 0.5 - 0.5 * (2.0 * PI * i as f32 / fft_size as f32).cos())
+                .collect(),
+            twiddles: (0..fft_size / 2)
+                .map(|k| {
+                    let angle = -2.0 * PI * k as f32 / fft_size as f32;
+                    (angle.cos(), angle.sin())
+                })
+                .collect(),
+            re: vec![0.0; fft_size],
+            im: vec![0.0; fft_size],
+            spectrum: vec![0.0; fft_size / 2],
+            prev_spectrum: vec![0.0; fft_size / 2],
+            levels: AudioLevels::default(),
+            flux: [0.0; FLUX_HISTORY],
+            flux_pos: 0,
+            cooldown_frames: 0,
+            onsets: 0,
+        }
+    }
+
+    /// Analyze the master mix, after all streams are mixed and master volume is applied
+    ///
+    /// Returns [`None`] if all tap slots are in use.
+    pub fn tap_master(_device: &'a AudioDevice, config: AnalyzerConfig) -> Option<Self> {
+        // SAFETY: The analyzer borrows the device
+        let tap = unsafe { AudioTap::attach(None, config.buffer_frames) }?;
+        Some(Self { tap: Some(tap), ..Self::new(config) })
+    }
+
+    /// Analyze a single stream, after its volume, pitch and pan are applied
+    ///
+    /// Returns [`None`] if all tap slots are in use.
+    pub fn tap_stream(stream: &'a AudioStream<'_>, config: AnalyzerConfig) -> Option<Self> {
+        // SAFETY: The analyzer borrows the stream, which borrows the device
+        let tap = unsafe { AudioTap::attach(Some(stream.as_sys()), config.buffer_frames) }?;
+        Some(Self { tap: Some(tap), ..Self::new(config) })
+    }
+
+    /// The settings this analyzer was created with
+    #[inline]
+    pub const fn config(&self) -> &AnalyzerConfig {
+        &self.config
+    }
+
+    /// Number of samples in each FFT window (after rounding)
+    #[inline]
+    pub const fn fft_size(&self) -> usize {
+        self.fft_size
+    }
+
+    /// Analyze everything the audio thread has delivered since the last update
+    ///
+    /// Resets the onset count.
+    pub fn update(&mut self) {
+        self.onsets = 0;
+        let Some((tap, mut consumer)) = self.tap.take() else { return };
+        // Only drain what is there now, so a fast producer can't keep us here forever
+        let mut remaining = consumer.len();
+        let mut chunk = [[0.0; 2]; 512];
+        while remaining > 0 {
+            let count = consumer.pop_slice(&mut chunk[..remaining.min(512)]);
+            self.push_frames(&chunk[..count]);
+            remaining -= count;
+        }
+        self.tap = Some((tap, consumer));
+    }
+
+    /// Feed stereo frames to the analyzer directly
+    ///
+    /// Tapped analyzers are fed by [`AudioAnalyzer::update()`]; this is for analyzing audio from other
+    /// sources, such as samples that were never sent to the audio device.
+    pub fn push_frames(&mut self, frames: &[[f32; 2]]) {
+        let hop = self.fft_size / 2;
+        for &[left, right] in frames {
+            self.history[self.history_pos] = (left + right) * 0.5;
+            self.history_pos = (self.history_pos + 1) % self.fft_size;
+            self.hop_peak = self.hop_peak.max(left.abs()).max(right.abs());
+            self.hop_sum_sq += left * left + right * right;
+            self.hop_frames += 1;
+            if self.hop_frames == hop {
+                self.analyze();
+            }
+        }
+    }
+
+    /// Compute spectrum, levels and onsets for the current window
+    fn analyze(&mut self) {
+        self.levels = AudioLevels {
+            peak: self.hop_peak,
+            rms: (self.hop_sum_sq / (self.hop_frames * 2) as f32).sqrt(),
+        };
+        self.cooldown_frames = self.cooldown_frames.saturating_sub(self.hop_frames);
+        self.hop_frames = 0;
+        self.hop_peak = 0.0;
+        self.hop_sum_sq = 0.0;
+
+        let (older, newer) = self.history.split_at(self.history_pos);
+        for ((re, sample), window) in self.re.iter_mut().zip(newer.iter().chain(older)).zip(&self.window) {
+            *re = sample * window;
+        }
+        self.im.fill(0.0);
+        fft(&mut self.re, &mut self.im, &self.twiddles);
+
+        std::mem::swap(&mut self.spectrum, &mut self.prev_spectrum);
+        // The Hann window sums to n/2; scale so a full-scale sine peaks near 1.0
+        let scale = 4.0 / self.fft_size as f32;
+        for (bin, (re, im)) in self.spectrum.iter_mut().zip(self.re.iter().zip(&self.im)) {
+            *bin = re.hypot(*im) * scale;
+        }
+
+        let flux: f32 = self.spectrum.iter()
+            .zip(&self.prev_spectrum)
+            .map(|(now, prev)| (now - prev).max(0.0))
+            .sum();
+        let average = self.flux.iter().sum::<f32>() / FLUX_HISTORY as f32;
+        self.flux[self.flux_pos] = flux;
+        self.flux_pos = (self.flux_pos + 1) % FLUX_HISTORY;
+        if self.cooldown_frames == 0 && flux >= self.config.onset_floor && flux > average * self.config.onset_threshold {
+            self.onsets += 1;
+            self.cooldown_frames = (self.config.onset_cooldown.as_secs_f32() * self.config.sample_rate as f32) as usize;
+        }
+    }
+
+    /// Magnitude of each frequency bin, `fft_size / 2` values
+    ///
+    /// Magnitudes are scaled so a full-scale sine centered on a bin measures about 1.0.
+    #[inline]
+    pub fn spectrum(&self) -> &[f32] {
+        &self.spectrum
+    }
+
+    /// Center frequency of a [spectrum](AudioAnalyzer::spectrum) bin in Hz
+    #[inline]
+    pub fn bin_frequency(&self, bin: usize) -> f32 {
+        bin as f32 * self.config.sample_rate as f32 / self.fft_size as f32
+    }
+
+    /// Peak and RMS level of the most recent half window
+    #[inline]
+    pub const fn levels(&self) -> AudioLevels {
+        self.levels
+    }
+
+    /// Number of onsets (sudden increases in spectral energy, such as beats) since the last update
+    #[inline]
+    pub const fn onsets(&self) -> u32 {
+        self.onsets
+    }
+
+    /// Whether an onset was detected since the last update
+    #[inline]
+    pub const fn is_onset(&self) -> bool {
+        self.onsets > 0
+    }
+
+    /// Number of frames the audio thread dropped because [`AudioAnalyzer::update()`] was not called often enough
+    #[inline]
+    pub fn dropped_frames(&self) -> u64 {
+        self.tap.as_ref().map_or(0, |(_, consumer)| consumer.overruns())
+    }
+}
+
+/// In-place iterative radix-2 FFT
+///
+/// `twiddles[k]` is `e^(-2πik/n)` for `k` in `0..n/2`.
+fn fft(re: &mut [f32], im: &mut [f32], twiddles: &[(f32, f32)]) {
+    let n = re.len();
+    debug_assert!(n.is_power_of_two() && im.len() == n && twiddles.len() == n / 2);
+
+    let mut j = 0;
+    for i in 1..n {
+        let mut bit = n >> 1;
+        while j & bit != 0 {
+            j ^= bit;
+            bit >>= 1;
+        }
+        j |= bit;
+        if i < j {
+            re.swap(i, j);
+            im.swap(i, j);
+        }
+    }
+
+    let mut len = 2;
+    while len <= n {
+        let half = len / 2;
+        let step = n / len;
+        for start in (0..n).step_by(len) {
+            for k in 0..half {
+                let (wr, wi) = twiddles[k * step];
+                let (a, b) = (start + k, start + k + half);
+                let tr = re[b] * wr - im[b] * wi;
+                let ti = re[b] * wi + im[b] * wr;
+                re[b] = re[a] - tr;
+                im[b] = im[a] - ti;
+                re[a] += tr;
+                im[a] += ti;
+            }
+        }
+        len <<= 1;
+    }
+}
@@ -0,0 +1,377 @@
+//! Audio device management
+
+use std::{ffi::{c_uint, c_void}, sync::{Mutex, atomic::{AtomicBool, AtomicU64, Ordering}}};
+use super::*;
+
+/// Whether an [`AudioDevice`] currently exists
+static AUDIO_DEVICE_OPEN: AtomicBool = AtomicBool::new(false);
+
+/// Handle to the initialized audio device
+///
+/// Closes the audio device upon exiting scope
+pub struct AudioDevice(());
+
+impl Drop for AudioDevice {
+    /// Close the audio device and context
+    #[inline]
+    fn drop(&mut self) {
+        // SAFETY: Existence of `AudioDevice` proves the audio device is initialized
+        unsafe {
+            low::close_audio_device();
+        }
+        AUDIO_DEVICE_OPEN.store(false, Ordering::Release);
+    }
+}
+
+impl AudioDevice {
+    /// Initialize audio device and context
+    ///
+    /// Returns [`None`] if an [`AudioDevice`] already exists or the device failed to initialize.
+    ///
+    /// With the `null_audio` feature, Raylib is built with only the miniaudio null backend,
+    /// which consumes samples in real time without a sound card. Combine it with
+    /// [`AudioDevice::capture_mixed_output()`] to inspect audio output in headless tests.
+    #[inline]
+    pub fn init() -> Option<Self> {
+        if AUDIO_DEVICE_OPEN.compare_exchange(false, true, Ordering::AcqRel, Ordering::Acquire).is_err() {
+            return None;
+        }
+        // SAFETY: `AUDIO_DEVICE_OPEN` guarantees no other `AudioDevice` exists
+        unsafe {
+            low::init_audio_device();
+        }
+        // SAFETY: `is_audio_device_ready` only reads the device state
+        if unsafe { low::is_audio_device_ready() } {
+            Some(Self(()))
+        } else {
+            AUDIO_DEVICE_OPEN.store(false, Ordering::Release);
+            None
+        }
+    }
+
+    /// Set master volume (listener)
+    ///
+    /// Takes `&self` so the volume can be changed while sounds and streams borrow the device.
+    /// miniaudio applies master volume atomically.
+    #[inline]
+    pub fn set_master_volume(&self, volume: f32) {
+        // SAFETY: Existence of `AudioDevice` proves the audio device is initialized
+        unsafe {
+            low::set_master_volume(volume);
+        }
+    }
+
+    /// Get master volume (listener)
+    #[inline]
+    pub fn get_master_volume(&self) -> f32 {
+        // SAFETY: Existence of `AudioDevice` proves the audio device is initialized
+        unsafe { low::get_master_volume() }
+    }
+
+    /// Start recording the mixed output sent to the audio device
+    ///
+    /// Samples are recorded after all streams have been mixed and master volume has been
+    /// applied, exactly as they are handed to the backend.
+    ///
+    /// Up to [`MIXED_CAPTURE_CAPACITY`] samples are kept between calls to [`MixedCapture::take()`];
+    /// the audio thread never blocks or allocates, and drops what does not fit instead.
+    ///
+    /// Returns [`None`] if a [`MixedCapture`] already exists.
+    #[inline]
+    pub fn capture_mixed_output(&self) -> Option<MixedCapture<'_>> {
+        if MIXED_CAPTURE_ACTIVE.swap(true, Ordering::AcqRel) {
+            return None;
+        }
+        let buffer = Vec::with_capacity(MIXED_CAPTURE_CAPACITY);
+        *MIXED_CAPTURE
+            .lock()
+            .unwrap_or_else(|e| e.into_inner()) = buffer;
+        MIXED_CAPTURE_DROPPED.store(0, Ordering::Relaxed);
+        // SAFETY:
+        // - Existence of `AudioDevice` proves the audio device is initialized
+        // - `MIXED_CAPTURE_ACTIVE` guarantees the processor is only attached once
+        unsafe {
+            low::attach_audio_mixed_processor(Some(capture_mixed_processor));
+        }
+        Some(MixedCapture(PhantomData))
+    }
+}
+
+/// Whether a [`MixedCapture`] currently exists
+static MIXED_CAPTURE_ACTIVE: AtomicBool = AtomicBool::new(false);
+
+/// Most interleaved samples a [`MixedCapture`] holds between takes (10 seconds of 48kHz stereo)
+pub const MIXED_CAPTURE_CAPACITY: usize = 48000 * 2 * 10;
+
+/// Interleaved stereo samples recorded by [`capture_mixed_processor`], with [`MIXED_CAPTURE_CAPACITY`] reserved
+static MIXED_CAPTURE: Mutex<Vec<f32>> = Mutex::new(Vec::new());
+
+/// Stereo frames [`capture_mixed_processor`] could not record
+static MIXED_CAPTURE_DROPPED: AtomicU64 = AtomicU64::new(0);
+
+/// Mixed processor that appends the master mix to [`MIXED_CAPTURE`]
+///
+/// Runs on the audio thread, so it only tries the lock and never grows the buffer past its reserved capacity.
+unsafe extern "C" fn capture_mixed_processor(buffer: *mut c_void, frames: c_uint) {
+    // SAFETY: Raylib passes `frames` stereo frames of 32-bit float samples
+    let samples = unsafe { std::slice::from_raw_parts(buffer.cast::<f32>().cast_const(), frames as usize * 2) };
+    let Ok(mut capture) = MIXED_CAPTURE.try_lock() else {
+        MIXED_CAPTURE_DROPPED.fetch_add(u64::from(frames), Ordering::Relaxed);
+        return;
+    };
+    let count = samples.len().min(MIXED_CAPTURE_CAPACITY.saturating_sub(capture.len()));
+    capture.extend_from_slice(&samples[..count]);
+    if count < samples.len() {
+        MIXED_CAPTURE_DROPPED.fetch_add(((samples.len() - count) / 2) as u64, Ordering::Relaxed);
+    }
+}
+
+/// Recording of the audio device's mixed output, see [`AudioDevice::capture_mixed_output()`]
+///
+/// Stops recording when dropped
+pub struct MixedCapture<'a>(PhantomData<&'a AudioDevice>);
+
+impl Drop for MixedCapture<'_> {
+    /// Detach the capture processor from the audio pipeline
+    #[inline]
+    fn drop(&mut self) {
+        // SAFETY:
+        // - `MixedCapture` borrows the `AudioDevice`, so the device is still initialized
+        // - Raylib holds the audio lock while detaching, so the processor is not running once this returns
+        unsafe {
+            low::detach_audio_mixed_processor(Some(capture_mixed_processor));
+        }
+        MIXED_CAPTURE_ACTIVE.store(false, Ordering::Release);
+    }
+}
+
+impl MixedCapture<'_> {
+    /// Number of stereo frames recorded since the last [`MixedCapture::take()`]
+    #[inline]
+    pub fn captured_frames(&self) -> usize {
+        MIXED_CAPTURE
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .len() / 2
+    }
+
+    /// Number of stereo frames dropped because the capture was full or being read by the game thread
+    #[inline]
+    pub fn dropped_frames(&self) -> u64 {
+        MIXED_CAPTURE_DROPPED.load(Ordering::Relaxed)
+    }
+
+    /// Take the samples recorded since the last call, as interleaved stereo frames (left, right, left, ...)
+    #[inline]
+    pub fn take(&mut self) -> Vec<f32> {
+        // Allocated before locking so the audio thread is not held up
+        let buffer = Vec::with_capacity(MIXED_CAPTURE_CAPACITY);
+        std::mem::replace(&mut *MIXED_CAPTURE
+            .lock()
+            .unwrap_or_else(|e| e.into_inner()), buffer)
+    }
+}
+
+/// Held while loading an [`AudioStream`], because [`AudioStream::load_with_buffer_size()`] changes Raylib's
+/// process-wide default buffer size for the duration of the load
+static STREAM_BUFFER_SIZE_LOCK: Mutex<()> = Mutex::new(());
+
+/// Audio stream that plays raw PCM data supplied by the application
+///
+/// Unloads the stream upon exiting scope.
+///
+/// Methods take `&self` because Raylib locks the stream's buffer internally,
+/// which lets analyzers and other taps borrow the stream while it is being fed.
+pub struct AudioStream<'a> {
+    stream: sys::AudioStream,
+    _device: PhantomData<&'a AudioDevice>,
+}
+
+impl Drop for AudioStream<'_> {
+    /// Unload audio stream and free memory
+    #[inline]
+    fn drop(&mut self) {
+        // SAFETY: `AudioStream` borrows the `AudioDevice` and `self.stream` was loaded by `AudioStream::load`
+        unsafe {
+            low::unload_audio_stream(self.stream);
+        }
+    }
+}
+
+impl<'a> AudioStream<'a> {
+    /// Load audio stream (to stream raw audio pcm data)
+    ///
+    /// `sample_size` is in bits and must be 8, 16 or 32 (32-bit samples are `f32`).
+    ///
+    /// Returns [`None`] if the format is not supported or the stream buffers could not be created.
+    pub fn load(device: &'a AudioDevice, sample_rate: u32, sample_size: u32, channels: u32) -> Option<Self> {
+        let _guard = STREAM_BUFFER_SIZE_LOCK.lock()
+            .unwrap_or_else(|e| e.into_inner());
+        Self::load_unguarded(device, sample_rate, sample_size, channels)
+    }
+
+    /// [`AudioStream::load()`] without taking [`STREAM_BUFFER_SIZE_LOCK`]
+    fn load_unguarded(_device: &'a AudioDevice, sample_rate: u32, sample_size: u32, channels: u32) -> Option<Self> {
+        if !matches!(sample_size, 8 | 16 | 32) || channels == 0 || sample_rate == 0 {
+            return None;
+        }
+        // SAFETY: Existence of `AudioDevice` proves the audio device is initialized
+        let stream = unsafe { low::load_audio_stream(sample_rate, sample_size, channels) };
+        // SAFETY: `is_audio_stream_valid` only reads the stream
+        if unsafe { low::is_audio_stream_valid(stream) } {
+            Some(Self { stream, _device: PhantomData })
+        } else {
+            None
+        }
+    }
+
+    /// Load audio stream with sub-buffers of `buffer_frames` frames instead of the device's default
+    ///
+    /// Raylib double-buffers streams, so up to two buffers are queued ahead of playback.
+    /// See [`AudioStream::load()`] for the other parameters.
+    pub fn load_with_buffer_size(
+        device: &'a AudioDevice,
+        sample_rate: u32,
+        sample_size: u32,
+        channels: u32,
+        buffer_frames: usize,
+    ) -> Option<Self> {
+        // Held until the default is restored, so streams loaded on other threads keep the default size
+        let _guard = STREAM_BUFFER_SIZE_LOCK.lock()
+            .unwrap_or_else(|e| e.into_inner());
+        // SAFETY: Existence of `AudioDevice` proves the audio device is initialized
+        unsafe {
+            low::set_audio_stream_buffer_size_default(buffer_frames);
+        }
+        let stream = Self::load_unguarded(device, sample_rate, sample_size, channels);
+        // SAFETY: Existence of `AudioDevice` proves the audio device is initialized;
+        // 0 restores Raylib's default (a 30th of a second at the device's sample rate)
+        unsafe {
+            low::set_audio_stream_buffer_size_default(0);
+        }
+        stream
+    }
+
+    #[inline]
+    pub(crate) const fn as_sys(&self) -> sys::AudioStream {
+        self.stream
+    }
+
+    /// Frequency (samples per second)
+    #[inline]
+    pub const fn sample_rate(&self) -> u32 {
+        self.stream.sampleRate
+    }
+
+    /// Bit depth (bits per sample): 8, 16, 32 (24 not supported)
+    #[inline]
+    pub const fn sample_size(&self) -> u32 {
+        self.stream.sampleSize
+    }
+
+    /// Number of channels (1-mono, 2-stereo, ...)
+    #[inline]
+    pub const fn channels(&self) -> u32 {
+        self.stream.channels
+    }
+
+    /// Size of one frame (one sample for every channel) in bytes
+    #[inline]
+    pub const fn frame_size(&self) -> usize {
+        (self.sample_size() / 8 * self.channels()) as usize
+    }
+
+    /// Update audio stream buffers with data
+    ///
+    /// `data` holds whole frames in the stream's format.
+    /// Only one buffer is updated per call; check [`AudioStream::is_processed()`] first.
+    ///
+    /// # Panics
+    ///
+    /// If the length of `data` is not a multiple of [`AudioStream::frame_size()`].
+    #[inline]
+    pub fn update(&self, data: &[u8]) {
+        assert!(data.len().is_multiple_of(self.frame_size()), "audio stream data should contain whole frames");
+        // SAFETY: `data` holds `data.len() / frame_size` frames in the stream's format
+        unsafe {
+            low::update_audio_stream(self.stream, data, data.len() / self.frame_size());
+        }
+    }
+
+    /// Check if any audio stream buffers requires refill
+    #[inline]
+    pub fn is_processed(&self) -> bool {
+        // SAFETY: `self.stream` is valid while `AudioStream` exists
+        unsafe { low::is_audio_stream_processed(self.stream) }
+    }
+
+    /// Play audio stream
+    #[inline]
+    pub fn play(&self) {
+        // SAFETY: `self.stream` is valid while `AudioStream` exists
+        unsafe {
+            low::play_audio_stream(self.stream);
+        }
+    }
+
+    /// Pause audio stream
+    #[inline]
+    pub fn pause(&self) {
+        // SAFETY: `self.stream` is valid while `AudioStream` exists
+        unsafe {
+            low::pause_audio_stream(self.stream);
+        }
+    }
+
+    /// Resume audio stream
+    #[inline]
+    pub fn resume(&self) {
+        // SAFETY: `self.stream` is valid while `AudioStream` exists
+        unsafe {
+            low::resume_audio_stream(self.stream);
+        }
+    }
+
+    /// Check if audio stream is playing
+    #[inline]
+    pub fn is_playing(&self) -> bool {
+        // SAFETY: `self.stream` is valid while `AudioStream` exists
+        unsafe { low::is_audio_stream_playing(self.stream) }
+    }
+
+    /// Stop audio stream
+    #[inline]
+    pub fn stop(&self) {
+        // SAFETY: `self.stream` is valid while `AudioStream` exists
+        unsafe {
+            low::stop_audio_stream(self.stream);
+        }
+    }
+
+    /// Set volume for audio stream (1.0 is max level)
+    #[inline]
+    pub fn set_volume(&self, volume: f32) {
+        // SAFETY: `self.stream` is valid while `AudioStream` exists
+        unsafe {
+            low::set_audio_stream_volume(self.stream, volume);
+        }
+    }
+
+    /// Set pitch for audio stream (1.0 is base level)
+    #[inline]
+    pub fn set_pitch(&self, pitch: f32) {
+        // SAFETY: `self.stream` is valid while `AudioStream` exists
+        unsafe {
+            low::set_audio_stream_pitch(self.stream, pitch);
+        }
+    }
+
+    /// Set pan for audio stream (0.5 is centered)
+    #[inline]
+    pub fn set_pan(&self, pan: f32) {
+        // SAFETY: `self.stream` is valid while `AudioStream` exists
+        unsafe {
+            low::set_audio_stream_pan(self.stream, pan);
+        }
+    }
+}
@@ -0,0 +1,464 @@
+//! Recording and replaying input
+//!
+//! [`InputRecorder`] records the input Raylib receives into [`AutomationEvents`], which can be saved with a bug report
+//! and replayed with [`InputPlayer`] for a reproducible run:
+//!
+//! ```ignore
+//! let mut recorder = InputRecorder::start(&mut window).expect("nothing else should be recording");
+//! while !window.should_close() && !recorder.is_full() {
+//!     window.draw(|rl, d, _m| game.frame(rl, d));
+//! }
+//! recorder.stop().save(Path::new("bug-1234.rae"))?;
+//!
+//! // Later, with the game reset to the same starting state:
+//! let mut player = InputPlayer::new(AutomationEvents::load(Path::new("bug-1234.rae"))?);
+//! while !window.should_close() && !player.is_finished() {
+//!     player.update(&mut window);
+//!     window.draw(|rl, d, _m| game.frame(rl, d));
+//! }
+//! ```
+//!
+//! Raylib only records and plays events when built with `SUPPORT_AUTOMATION_EVENTS` (the default).
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use super::*;
+use into_cstr::IntoCStrNulError;
+
+/// Number of events an [`InputRecorder`] can hold (`MAX_AUTOMATION_EVENTS` in `config.h`)
+pub const MAX_AUTOMATION_EVENTS: usize = 16384;
+
+/// Kinds of [`AutomationEvent`] (`AutomationEventType` in `rcore.c`)
+#[repr(u32)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AutomationEventKind {
+    /// Key is up, `params[0]`: key
+    KeyUp = 1,
+    /// Key is down, `params[0]`: key
+    KeyDown = 2,
+    /// Key was pressed, `params[0]`: key
+    KeyPressed = 3,
+    /// Key was released, `params[0]`: key
+    KeyReleased = 4,
+    /// Mouse button is up, `params[0]`: button
+    MouseButtonUp = 5,
+    /// Mouse button is down, `params[0]`: button
+    MouseButtonDown = 6,
+    /// Mouse moved, `params[0]`: x, `params[1]`: y
+    MousePosition = 7,
+    /// Mouse wheel moved, `params[0]`: x delta, `params[1]`: y delta
+    MouseWheelMotion = 8,
+    /// Gamepad connected, `params[0]`: gamepad
+    GamepadConnect = 9,
+    /// Gamepad disconnected, `params[0]`: gamepad
+    GamepadDisconnect = 10,
+    /// Gamepad button is up, `params[0]`: button
+    GamepadButtonUp = 11,
+    /// Gamepad button is down, `params[0]`: button
+    GamepadButtonDown = 12,
+    /// Gamepad axis moved, `params[0]`: axis, `params[1]`: delta
+    GamepadAxisMotion = 13,
+    /// Touch point lifted, `params[0]`: id
+    TouchUp = 14,
+    /// Touch point down, `params[0]`: id
+    TouchDown = 15,
+    /// Touch point moved, `params[0]`: x, `params[1]`: y
+    TouchPosition = 16,
+    /// Gesture detected, `params[0]`: gesture
+    Gesture = 17,
+    /// Window close requested
+    WindowClose = 18,
+    /// Window maximized
+    WindowMaximize = 19,
+    /// Window minimized
+    WindowMinimize = 20,
+    /// Window resized, `params[0]`: width, `params[1]`: height
+    WindowResize = 21,
+    /// Screenshot taken
+    TakeScreenshot = 22,
+    /// Target FPS set, `params[0]`: fps
+    SetTargetFps = 23,
+}
+
+impl AutomationEventKind {
+    /// Every kind, in discriminant order
+    pub const ALL: [Self; 23] = [
+        Self::KeyUp, Self::KeyDown, Self::KeyPressed, Self::KeyReleased,
+        Self::MouseButtonUp, Self::MouseButtonDown, Self::MousePosition, Self::MouseWheelMotion,
+        Self::GamepadConnect, Self::GamepadDisconnect, Self::GamepadButtonUp, Self::GamepadButtonDown, Self::GamepadAxisMotion,
+        Self::TouchUp, Self::TouchDown, Self::TouchPosition, Self::Gesture,
+        Self::WindowClose, Self::WindowMaximize, Self::WindowMinimize, Self::WindowResize,
+        Self::TakeScreenshot, Self::SetTargetFps,
+    ];
+
+    /// Check if this is keyboard, mouse, gamepad, touch or gesture input, as opposed to a window event or action
+    #[inline]
+    pub const fn is_input(self) -> bool {
+        (self as u32) <= Self::Gesture as u32
+    }
+}
+
+impl TryFrom<u32> for AutomationEventKind {
+    type Error = ();
+
+    #[inline]
+    fn try_from(value: u32) -> Result<Self, Self::Error> {
+        value.checked_sub(1)
+            .and_then(|index| Self::ALL.get(index as usize))
+            .copied()
+            .ok_or(())
+    }
+}
+
+/// One recorded input or window event
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct AutomationEvent {
+    /// Frame the event happened in, counted from the start of the recording
+    pub frame: u32,
+    /// What happened
+    pub kind: AutomationEventKind,
+    /// Parameters, see [`AutomationEventKind`]
+    pub params: [i32; 4],
+}
+
+impl AutomationEvent {
+    /// [`None`] for event types this crate does not know
+    #[inline]
+    fn from_sys(event: sys::AutomationEvent) -> Option<Self> {
+        Some(Self {
+            frame: event.frame,
+            kind: event.type_.try_into().ok()?,
+            params: event.params,
+        })
+    }
+
+    #[inline]
+    const fn into_sys(self) -> sys::AutomationEvent {
+        sys::AutomationEvent {
+            frame: self.frame,
+            type_: self.kind as u32,
+            params: self.params,
+        }
+    }
+}
+
+/// An error from loading or saving [`AutomationEvents`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AutomationEventsError {
+    /// The file name contained a nul byte
+    InvalidPath(IntoCStrNulError),
+    /// The file does not exist
+    LoadFailed,
+    /// Raylib could not write the file
+    ExportFailed,
+}
+
+impl From<IntoCStrNulError> for AutomationEventsError {
+    #[inline]
+    fn from(value: IntoCStrNulError) -> Self {
+        Self::InvalidPath(value)
+    }
+}
+
+impl std::fmt::Display for AutomationEventsError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::InvalidPath(e) => write!(f, "invalid path: {e}"),
+            Self::LoadFailed => f.write_str("failed to load automation events"),
+            Self::ExportFailed => f.write_str("failed to export automation events"),
+        }
+    }
+}
+
+impl std::error::Error for AutomationEventsError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::InvalidPath(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+/// Recorded events, ordered by frame
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct AutomationEvents {
+    events: Vec<AutomationEvent>,
+}
+
+impl AutomationEvents {
+    /// No events
+    #[inline]
+    pub const fn new() -> Self {
+        Self { events: Vec::new() }
+    }
+
+    /// Copy the events out of a Raylib list
+    ///
+    /// # Safety
+    /// - `list.events` must point to at least `list.count` events, or be null if `list.count` is 0
+    unsafe fn from_sys(list: &sys::AutomationEventList) -> Self {
+        if list.events.is_null() {
+            return Self::new();
+        }
+        // SAFETY: Caller guarantees `list.events` points to `list.count` events
+        let events = unsafe { std::slice::from_raw_parts(list.events, list.count as usize) };
+        events.iter().copied().filter_map(AutomationEvent::from_sys).collect()
+    }
+
+    /// Load events from a file exported by [`AutomationEvents::save()`] or Raylib's `ExportAutomationEventList`
+    ///
+    /// Events of kinds this crate does not know are skipped.
+    /// Raylib reads a file it cannot open as an empty list, so only a missing file is reported as
+    /// [`AutomationEventsError::LoadFailed`]; an empty recording loads as an empty list.
+    pub fn load(file_name: impl IntoCStr) -> Result<Self, AutomationEventsError> {
+        let file_name = file_name.into_cstr()?;
+        // SAFETY: Checking for a file only queries the file system; it does not require a window
+        if !unsafe { low::file_exists(file_name.as_ref()) } {
+            return Err(AutomationEventsError::LoadFailed);
+        }
+        // SAFETY: Loading a list only reads the file and allocates; it does not require a window
+        let list = unsafe { low::load_automation_event_list(file_name.as_ref()) };
+        // SAFETY: Raylib allocated `list.events` for `list.capacity` events and filled `list.count` of them
+        let events = unsafe { Self::from_sys(&list) };
+        // SAFETY: `list` was allocated by `load_automation_event_list` and is not used after this
+        unsafe {
+            low::unload_automation_event_list(list);
+        }
+        Ok(events)
+    }
+
+    /// Save the events as a text file (`.rae` by convention)
+    ///
+    /// # Panics
+    /// - There are more than [`u32::MAX`] events
+    pub fn save(&self, file_name: impl IntoCStr) -> Result<(), AutomationEventsError> {
+        let file_name = file_name.into_cstr()?;
+        let mut events: Vec<sys::AutomationEvent> = self.events.iter().map(|event| event.into_sys()).collect();
+        let count = events.len().try_into().unwrap();
+        let list = sys::AutomationEventList {
+            capacity: count,
+            count,
+            events: events.as_mut_ptr(),
+        };
+        // SAFETY: Exporting only reads `list.count` events from `list.events`, which `events` keeps alive
+        match unsafe { low::export_automation_event_list(list, file_name.as_ref()) } {
+            true => Ok(()),
+            false => Err(AutomationEventsError::ExportFailed),
+        }
+    }
+
+    /// Number of events
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.events.len()
+    }
+
+    /// Check if there are no events
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.events.is_empty()
+    }
+
+    /// The events, ordered by frame
+    #[inline]
+    pub fn as_slice(&self) -> &[AutomationEvent] {
+        &self.events
+    }
+
+    /// Iterate over the events, ordered by frame
+    #[inline]
+    pub fn iter(&self) -> std::slice::Iter<'_, AutomationEvent> {
+        self.events.iter()
+    }
+
+    /// Events of one kind
+    #[inline]
+    pub fn of_kind(&self, kind: AutomationEventKind) -> impl Iterator<Item = &AutomationEvent> {
+        self.events.iter().filter(move |event| event.kind == kind)
+    }
+
+    /// Keep only the events for which `f` returns true
+    #[inline]
+    pub fn retain(&mut self, f: impl FnMut(&AutomationEvent) -> bool) {
+        self.events.retain(f);
+    }
+
+    /// Add an event, keeping the events ordered by frame
+    pub fn push(&mut self, event: AutomationEvent) {
+        let index = self.events.partition_point(|e| e.frame <= event.frame);
+        self.events.insert(index, event);
+    }
+
+    /// Number of frames the recording spans, from frame 0 to the last event
+    #[inline]
+    pub fn frame_count(&self) -> u32 {
+        self.events.last().map_or(0, |event| event.frame + 1)
+    }
+}
+
+impl FromIterator<AutomationEvent> for AutomationEvents {
+    /// Collect events in any order
+    fn from_iter<T: IntoIterator<Item = AutomationEvent>>(iter: T) -> Self {
+        let mut events: Vec<AutomationEvent> = iter.into_iter().collect();
+        events.sort_by_key(|event| event.frame);
+        Self { events }
+    }
+}
+
+impl IntoIterator for AutomationEvents {
+    type Item = AutomationEvent;
+    type IntoIter = std::vec::IntoIter<AutomationEvent>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.events.into_iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a AutomationEvents {
+    type Item = &'a AutomationEvent;
+    type IntoIter = std::slice::Iter<'a, AutomationEvent>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.events.iter()
+    }
+}
+
+/// Whether an [`InputRecorder`] exists, since Raylib records into a single global list
+static RECORDING: AtomicBool = AtomicBool::new(false);
+
+/// Records the input Raylib receives, see the [module docs](crate::safe::automation)
+///
+/// Only one can exist at a time. Dropping it without [`InputRecorder::stop()`] discards the events.
+pub struct InputRecorder {
+    /// Allocated by Raylib and registered with `SetAutomationEventList` until dropped
+    list: NonNull<sys::AutomationEventList>,
+}
+
+impl InputRecorder {
+    /// Start recording; events are recorded each time input is polled, counting frames from 0
+    ///
+    /// Returns [`None`] if another [`InputRecorder`] exists.
+    pub fn start(_rl: &mut WindowInner<'_>) -> Option<Self> {
+        if RECORDING.swap(true, Ordering::Acquire) {
+            return None;
+        }
+        // SAFETY: Creating a list only allocates
+        let list = unsafe { low::new_automation_event_list() };
+        let list = NonNull::from(Box::leak(Box::new(list)));
+        // SAFETY:
+        // - Existence of `WindowInner` proves Raylib is initialized
+        // - `list` stays valid and registered until this recorder is dropped, which unregisters it first
+        unsafe {
+            low::set_automation_event_list(Some(&mut *list.as_ptr()));
+            low::set_automation_event_base_frame(0);
+            low::start_automation_event_recording();
+        }
+        Some(Self { list })
+    }
+
+    /// Number of events recorded so far
+    #[inline]
+    pub fn len(&self) -> usize {
+        // SAFETY: `list` is valid while `self` exists, and Raylib only writes to it while polling input,
+        // which needs the window borrowed exclusively
+        unsafe { self.list.as_ref() }.count as usize
+    }
+
+    /// Check if nothing has been recorded yet
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Check if the recording is full; Raylib drops any further events
+    #[inline]
+    pub fn is_full(&self) -> bool {
+        // SAFETY: See `InputRecorder::len()`
+        let list = unsafe { self.list.as_ref() };
+        list.count >= list.capacity
+    }
+
+    /// Stop recording and take the events
+    pub fn stop(self) -> AutomationEvents {
+        // SAFETY: See `InputRecorder::len()`; Raylib allocated `events` for `capacity` events and filled `count`
+        unsafe { AutomationEvents::from_sys(self.list.as_ref()) }
+    }
+}
+
+impl Drop for InputRecorder {
+    fn drop(&mut self) {
+        // SAFETY:
+        // - Stopping and unregistering only clear Raylib's recording state, even if the window has closed since
+        // - `list` was leaked from a box in `start` and Raylib no longer refers to it once unregistered
+        unsafe {
+            low::stop_automation_event_recording();
+            low::set_automation_event_list(None);
+            let list = Box::from_raw(self.list.as_ptr());
+            low::unload_automation_event_list(*list);
+        }
+        RECORDING.store(false, Ordering::Release);
+    }
+}
+
+/// Replays [`AutomationEvents`] one frame at a time, see the [module docs](crate::safe::automation)
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct InputPlayer {
+    events: AutomationEvents,
+    next: usize,
+    frame: u32,
+}
+
+impl InputPlayer {
+    /// Play `events` from frame 0
+    #[inline]
+    pub const fn new(events: AutomationEvents) -> Self {
+        Self { events, next: 0, frame: 0 }
+    }
+
+    /// The events being played
+    #[inline]
+    pub const fn events(&self) -> &AutomationEvents {
+        &self.events
+    }
+
+    /// Frame that the next update plays
+    #[inline]
+    pub const fn frame(&self) -> u32 {
+        self.frame
+    }
+
+    /// Check if every event has been played
+    #[inline]
+    pub fn is_finished(&self) -> bool {
+        self.next >= self.events.len()
+    }
+
+    /// Play from frame 0 again
+    #[inline]
+    pub fn restart(&mut self) {
+        self.next = 0;
+        self.frame = 0;
+    }
+
+    /// Take the events due this frame and move to the next frame, without playing them
+    pub fn advance(&mut self) -> &[AutomationEvent] {
+        let start = self.next;
+        let frame = self.frame;
+        // Events from frames already passed are played late rather than never
+        let due = self.events.events[start..].partition_point(|event| event.frame <= frame);
+        self.next += due;
+        self.frame += 1;
+        &self.events.events[start..self.next]
+    }
+
+    /// Play the events due this frame; call once per frame, before reading input
+    pub fn update(&mut self, _rl: &mut WindowInner<'_>) {
+        for event in self.advance() {
+            // SAFETY: Existence of `WindowInner` proves Raylib is initialized
+            unsafe {
+                low::play_automation_event(event.into_sys());
+            }
+        }
+    }
+}
@@ -0,0 +1,234 @@
+//! Redrawing only when something changes
+//!
+//! Tool applications spend most of their time idle. [`Window::run_event_loop()`] lets Raylib block
+//! until the next window event (input, resize, file drop, ...) instead of drawing at a fixed rate,
+//! and polls again only while the application asks for it.
+//!
+//! ```ignore
+//! let mut caret_visible = true;
+//! rl.run_event_loop(EventLoop::new(), |event_loop, wake, rl, d, _m| {
+//!     if wake == Wake::Timer {
+//!         caret_visible = !caret_visible;
+//!     }
+//!     event_loop.wake_after(Duration::from_millis(500));
+//!     d.clear_background(Color::RAYWHITE);
+//!     // ...
+//! });
+//! ```
+
+use std::time::Instant;
+use super::*;
+
+/// Why a frame of [`Window::run_event_loop()`] is being drawn
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Wake {
+    /// The first frame of the loop
+    Start,
+    /// A window event arrived: input, resize, file drop, focus change, ...
+    Event,
+    /// [`EventLoop::request_redraw()`] was called during the previous frame
+    Redraw,
+    /// A deadline from [`EventLoop::wake_after()`] or [`EventLoop::wake_at()`] passed
+    Timer,
+    /// [`EventLoop::set_continuous()`] is on
+    Continuous,
+    /// Polled while a deadline is pending, whether or not any event arrived
+    Poll,
+}
+
+/// How the current frame of [`Window::run_event_loop()`] ends
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum LoopMode {
+    /// Poll events and start the next frame at [`EventLoop::fps()`]
+    Continuous,
+    /// Poll events and start the next frame at [`EventLoop::poll_fps()`], to catch a pending deadline
+    ///
+    /// Raylib can block until the next event but not with a timeout, so deadlines are polled for.
+    Poll,
+    /// Block until the next window event
+    Wait,
+}
+
+/// Redraw scheduling for [`Window::run_event_loop()`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EventLoop {
+    fps: u32,
+    poll_fps: u32,
+    continuous: bool,
+    redraw: bool,
+    deadline: Option<Instant>,
+    exit: bool,
+}
+
+impl Default for EventLoop {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl EventLoop {
+    /// Event-driven scheduling, drawing at up to 60 FPS while active and polling at 20 FPS for deadlines
+    #[inline]
+    pub const fn new() -> Self {
+        Self {
+            fps: 60,
+            poll_fps: 20,
+            continuous: false,
+            redraw: false,
+            deadline: None,
+            exit: false,
+        }
+    }
+
+    /// Set the frame rate while drawing continuously or handling events
+    #[inline]
+    pub const fn with_fps(mut self, fps: u32) -> Self {
+        self.fps = fps;
+        self
+    }
+
+    /// Set the frame rate while polling for a pending deadline, which bounds how late a timer can fire
+    #[inline]
+    pub const fn with_poll_fps(mut self, fps: u32) -> Self {
+        self.poll_fps = fps;
+        self
+    }
+
+    /// Frame rate while drawing continuously or handling events
+    #[inline]
+    pub const fn fps(&self) -> u32 {
+        self.fps
+    }
+
+    /// Frame rate while polling for a pending deadline
+    #[inline]
+    pub const fn poll_fps(&self) -> u32 {
+        self.poll_fps
+    }
+
+    /// Draw another frame right after this one, even if no event arrives
+    #[inline]
+    pub fn request_redraw(&mut self) {
+        self.redraw = true;
+    }
+
+    /// Draw a frame once `delay` has passed, even if no event arrives
+    ///
+    /// Only the earliest pending deadline is kept.
+    #[inline]
+    pub fn wake_after(&mut self, delay: Duration) {
+        self.wake_at(Instant::now() + delay);
+    }
+
+    /// Draw a frame once `deadline` has passed, even if no event arrives
+    ///
+    /// Only the earliest pending deadline is kept.
+    #[inline]
+    pub fn wake_at(&mut self, deadline: Instant) {
+        self.deadline = Some(self.deadline.map_or(deadline, |pending| pending.min(deadline)));
+    }
+
+    /// Cancel the pending deadline
+    #[inline]
+    pub fn cancel_wake(&mut self) {
+        self.deadline = None;
+    }
+
+    /// The pending deadline, if any
+    #[inline]
+    pub const fn deadline(&self) -> Option<Instant> {
+        self.deadline
+    }
+
+    /// Draw every frame, such as while an animation is running, or go back to waiting for events
+    #[inline]
+    pub fn set_continuous(&mut self, continuous: bool) {
+        self.continuous = continuous;
+    }
+
+    /// Whether every frame is drawn
+    #[inline]
+    pub const fn is_continuous(&self) -> bool {
+        self.continuous
+    }
+
+    /// Stop the loop after this frame
+    #[inline]
+    pub fn exit(&mut self) {
+        self.exit = true;
+    }
+
+    /// How a frame ending at `now` would end
+    pub fn mode(&self, now: Instant) -> LoopMode {
+        if self.continuous || self.redraw || self.deadline.is_some_and(|deadline| deadline <= now) {
+            LoopMode::Continuous
+        } else if self.deadline.is_some() {
+            LoopMode::Poll
+        } else {
+            LoopMode::Wait
+        }
+    }
+
+    /// Why the frame starting at `now` is drawn, after a frame that ended in `mode`
+    ///
+    /// Clears the redraw request and an expired deadline.
+    fn wake(&mut self, mode: LoopMode, now: Instant) -> Wake {
+        if self.deadline.is_some_and(|deadline| deadline <= now) {
+            self.deadline = None;
+            // The timer's frame serves a pending redraw as well
+            self.redraw = false;
+            Wake::Timer
+        } else if std::mem::take(&mut self.redraw) {
+            Wake::Redraw
+        } else {
+            match mode {
+                LoopMode::Continuous => Wake::Continuous,
+                LoopMode::Poll => Wake::Poll,
+                LoopMode::Wait => Wake::Event,
+            }
+        }
+    }
+}
+
+impl<'ctx> Window<'ctx> {
+    /// Draw frames until the window should close or [`EventLoop::exit()`] is called, sleeping while idle
+    ///
+    /// `f` is called once per frame with the scheduler and the reason for the frame.
+    /// At the end of each frame the loop enables event waiting if nothing asked for another frame,
+    /// and disables it otherwise; event waiting is disabled again when the loop returns.
+    ///
+    /// Any target FPS set before the loop is replaced by [`EventLoop::fps()`].
+    pub fn run_event_loop(
+        &mut self,
+        mut event_loop: EventLoop,
+        mut f: impl FnMut(&mut EventLoop, Wake, &mut WindowInner<'ctx>, &mut Drawing, &mut BaseDrawMode),
+    ) {
+        let mut wake = Wake::Start;
+        while !self.should_close() && !event_loop.exit {
+            let mut mode = LoopMode::Continuous;
+            self.draw(|rl, d, m| {
+                f(&mut event_loop, wake, rl, d, m);
+                // The frame ends when `d` drops, so this decides whether ending it polls or waits
+                mode = event_loop.mode(Instant::now());
+                match mode {
+                    LoopMode::Continuous => {
+                        rl.disable_event_waiting();
+                        rl.set_target_fps(event_loop.fps);
+                    }
+                    LoopMode::Poll => {
+                        rl.disable_event_waiting();
+                        rl.set_target_fps(event_loop.poll_fps);
+                    }
+                    LoopMode::Wait => {
+                        rl.enable_event_waiting();
+                        rl.set_target_fps(event_loop.fps);
+                    }
+                }
+            });
+            wake = event_loop.wake(mode, Instant::now());
+        }
+        self.disable_event_waiting();
+        self.set_target_fps(event_loop.fps);
+    }
+}
@@ -0,0 +1,170 @@
+//! Drawing, presenting and polling as separate steps
+//!
+//! With the `custom_frame_control` feature, ending a frame no longer swaps buffers, waits for the
+//! target FPS or polls input. [`Window::render()`] returns a [`RenderedFrame`] that is only shown by
+//! [`Window::present()`], and [`Window::poll_input_events()`] can be called as often as needed.
+//!
+//! Raylib skips its frame timing in this mode, so it is kept here instead: [`Window::present()`] measures the frame
+//! for [`WindowInner::get_frame_duration()`] and [`WindowInner::get_fps()`], and [`Window::draw()`] also waits out
+//! the rest of [`WindowInner::set_target_fps()`] before polling input, like it does without the feature.
+//!
+//! A fixed-timestep simulation that reads input before every step and interpolates when drawing:
+//!
+//! ```ignore
+//! const STEP: Duration = Duration::from_micros(16_667);
+//! let mut previous = Instant::now();
+//! let mut lag = Duration::ZERO;
+//! while !rl.should_close() {
+//!     let now = Instant::now();
+//!     lag += (now - previous).min(Duration::from_millis(250));
+//!     previous = now;
+//!     while lag >= STEP {
+//!         rl.poll_input_events();
+//!         state.update(&rl, STEP);
+//!         lag -= STEP;
+//!     }
+//!     let alpha = lag.as_secs_f32() / STEP.as_secs_f32();
+//!     let frame = rl.render(|_rl, d, _m| state.draw(d, alpha));
+//!     rl.present(frame);
+//! }
+//! ```
+
+use std::time::Instant;
+use super::*;
+
+/// Number of frames [`WindowInner::get_fps()`] averages over (`FPS_CAPTURE_FRAMES_COUNT` in `rcore.c`)
+const FPS_FRAMES: usize = 30;
+
+/// Frame timing that Raylib skips with `custom_frame_control`
+struct FrameClock {
+    /// Minimum frame duration, [`None`] for no limit
+    target: Option<Duration>,
+    /// When the current frame started, [`None`] before the first frame
+    frame_start: Option<Instant>,
+    /// Durations of the last frames, the latest at `next - 1`
+    frames: [Duration; FPS_FRAMES],
+    next: usize,
+}
+
+/// There is only one window, see [`WindowContext::init()`]
+static FRAME_CLOCK: Mutex<FrameClock> = Mutex::new(FrameClock {
+    target: None,
+    frame_start: None,
+    frames: [Duration::ZERO; FPS_FRAMES],
+    next: 0,
+});
+
+fn frame_clock() -> std::sync::MutexGuard<'static, FrameClock> {
+    FRAME_CLOCK.lock()
+        .unwrap_or_else(PoisonError::into_inner)
+}
+
+/// Forget the frames of a previous window, keeping the target
+pub(crate) fn reset_frame_clock() {
+    let mut clock = frame_clock();
+    clock.frame_start = None;
+    clock.frames = [Duration::ZERO; FPS_FRAMES];
+    clock.next = 0;
+}
+
+/// Record the target set by [`WindowInner::set_target_fps()`]
+pub(crate) fn set_target_fps(fps: u32) {
+    frame_clock().target = (fps > 0).then(|| Duration::from_secs_f64(1.0 / f64::from(fps)));
+}
+
+/// Duration of the last frame ended by [`end_frame()`]
+pub(crate) fn frame_duration() -> Duration {
+    let clock = frame_clock();
+    clock.frames[(clock.next + FPS_FRAMES - 1) % FPS_FRAMES]
+}
+
+/// Frames per second averaged over the last [`FPS_FRAMES`] frames
+pub(crate) fn fps() -> u32 {
+    let clock = frame_clock();
+    let total: Duration = clock.frames.iter().sum();
+    let count = clock.frames.iter().filter(|frame| !frame.is_zero()).count();
+    if total.is_zero() {
+        0
+    } else {
+        (count as f64 / total.as_secs_f64()).round() as u32
+    }
+}
+
+/// End the current frame, calling `wait` with the time left until the target frame duration, if any
+///
+/// The first frame only starts the clock.
+pub(crate) fn end_frame(wait: impl FnOnce(Duration)) {
+    let mut clock = frame_clock();
+    let Some(start) = clock.frame_start else {
+        clock.frame_start = Some(Instant::now());
+        return;
+    };
+    if let Some(remaining) = clock.target.and_then(|target| target.checked_sub(start.elapsed())) {
+        wait(remaining);
+    }
+    let now = Instant::now();
+    let next = clock.next;
+    clock.frames[next] = now - start;
+    clock.next = (next + 1) % FPS_FRAMES;
+    clock.frame_start = Some(now);
+}
+
+/// A frame drawn by [`Window::render()`] that is not on screen yet
+///
+/// Dropping it discards the frame; the next [`Window::render()`] draws over it.
+#[must_use = "the frame is only shown by `Window::present()`"]
+pub struct RenderedFrame(NotSendSync);
+
+impl<'ctx> Window<'ctx> {
+    /// Draw a frame to the back buffer without showing it or polling input
+    #[inline]
+    pub fn render<'w>(&'w mut self, f: impl for<'d> FnOnce(&'w mut WindowInner<'ctx>, &'d mut Drawing, &'d mut BaseDrawMode)) -> RenderedFrame {
+        // SAFETY:
+        // - Existence of `Window` proves window is initialized
+        // - `Window` is borrowed exclusively, and `WindowInner` (which cannot create draw handles) is passed to `f` instead
+        unsafe {
+            low::begin_drawing();
+        }
+        f(&mut self.0, &mut Drawing(PhantomData), &mut BaseDrawMode(PhantomData));
+        RenderedFrame(PhantomData)
+    }
+
+    /// Show a rendered frame (swap back buffer with front buffer) and measure the frame
+    ///
+    /// Waits for vertical sync if [`ConfigFlags::VSYNC_HINT`](low::types::ConfigFlags::VSYNC_HINT) is in effect,
+    /// but not for the target FPS; pace frames with [`Window::wait()`].
+    #[inline]
+    pub fn present(&mut self, frame: RenderedFrame) {
+        let RenderedFrame(_) = frame;
+        // SAFETY:
+        // - Existence of `Window` proves window is initialized
+        // - `RenderedFrame` is only returned once drawing has ended
+        unsafe {
+            low::swap_screen_buffer();
+        }
+        end_frame(|_| {});
+    }
+
+    /// Register all input events
+    ///
+    /// Pressed and released queries such as [`WindowInner::is_key_pressed()`] compare against the previous poll,
+    /// so each poll starts a new input step.
+    #[inline]
+    pub fn poll_input_events(&mut self) {
+        // SAFETY:
+        // - Existence of `Window` proves window is initialized
+        // - `Window` is borrowed exclusively, so this is not called while drawing
+        unsafe {
+            low::poll_input_events();
+        }
+    }
+
+    /// Halt the program for `duration`
+    #[inline]
+    pub fn wait(&mut self, duration: Duration) {
+        // SAFETY: Existence of `Window` proves window is initialized
+        unsafe {
+            low::wait_duration(duration);
+        }
+    }
+}
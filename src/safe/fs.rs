@@ -0,0 +1,115 @@
+//! File system helpers returning owned paths
+//!
+//! Raylib returns some paths in static buffers, which [`low`] guards with singleton handles such as
+//! [`low::GetDirectoryPathHandle`]. The functions here hold those handles behind locks and copy the result out,
+//! so they can be called any number of times, from any thread:
+//!
+//! ```ignore
+//! let assets = get_application_directory().unwrap().join("assets");
+//! let level = get_file_name_without_ext(Path::new("levels/forest.tmx")); // Some("forest")
+//! ```
+//!
+//! Taking a handle from [`low`] directly before the matching function here is first called makes that function panic;
+//! once the function has claimed it, the handle's `get()` returns [`None`].
+
+use std::{ffi::{CStr, OsString}, path::PathBuf, sync::LazyLock};
+use super::*;
+
+static FILE_NAME_WITHOUT_EXT: LazyLock<Mutex<Option<low::GetFileNameWithoutExtHandle>>> =
+    LazyLock::new(|| Mutex::new(low::GetFileNameWithoutExtHandle::get()));
+static DIRECTORY_PATH: LazyLock<Mutex<Option<low::GetDirectoryPathHandle>>> =
+    LazyLock::new(|| Mutex::new(low::GetDirectoryPathHandle::get()));
+static PREV_DIRECTORY_PATH: LazyLock<Mutex<Option<low::GetPrevDirectoryPathHandle>>> =
+    LazyLock::new(|| Mutex::new(low::GetPrevDirectoryPathHandle::get()));
+static WORKING_DIRECTORY: LazyLock<Mutex<Option<low::GetWorkingDirectoryHandle>>> =
+    LazyLock::new(|| Mutex::new(low::GetWorkingDirectoryHandle::get()));
+static APPLICATION_DIRECTORY: LazyLock<Mutex<Option<low::GetApplicationDirectoryHandle>>> =
+    LazyLock::new(|| Mutex::new(low::GetApplicationDirectoryHandle::get()));
+
+/// Call `f` with a buffer handle, holding its lock until the result has been copied out
+///
+/// # Panics
+/// - The handle was taken from [`low`] before this module could claim it
+fn with_handle<H, R>(handle: &Mutex<Option<H>>, name: &str, f: impl FnOnce(&mut H) -> R) -> R {
+    let mut handle = handle.lock()
+        .unwrap_or_else(PoisonError::into_inner);
+    let handle = handle.as_mut()
+        .unwrap_or_else(|| panic!("`low::{name}` should not be obtained elsewhere"));
+    f(handle)
+}
+
+/// Copy a path returned by Raylib
+fn to_os_string(s: &CStr) -> OsString {
+    #[cfg(unix)]
+    {
+        use std::os::unix::ffi::OsStrExt;
+        std::ffi::OsStr::from_bytes(s.to_bytes()).to_owned()
+    }
+    #[cfg(not(unix))]
+    {
+        OsString::from(s.to_string_lossy().into_owned())
+    }
+}
+
+/// Get filename string without extension
+///
+/// # Panics
+/// - [`IntoCStr::into_cstr()`] fails for `file_path`
+/// - [`low::GetFileNameWithoutExtHandle`] was obtained elsewhere
+pub fn get_file_name_without_ext(file_path: impl IntoCStr) -> Option<OsString> {
+    let file_path = file_path.into_cstr().unwrap();
+    with_handle(&FILE_NAME_WITHOUT_EXT, "GetFileNameWithoutExtHandle", |handle| {
+        // SAFETY: The handle's lock is held until the buffer has been copied
+        unsafe { handle.get_file_name_without_ext(file_path.as_ref()) }.map(to_os_string)
+    })
+}
+
+/// Get full path for a given fileName with path
+///
+/// Relative paths are made relative to `.`, such as `./assets` for `assets/hero.png`.
+///
+/// # Panics
+/// - [`IntoCStr::into_cstr()`] fails for `file_path`
+/// - [`low::GetDirectoryPathHandle`] was obtained elsewhere
+pub fn get_directory_path(file_path: impl IntoCStr) -> Option<PathBuf> {
+    let file_path = file_path.into_cstr().unwrap();
+    with_handle(&DIRECTORY_PATH, "GetDirectoryPathHandle", |handle| {
+        // SAFETY: The handle's lock is held until the buffer has been copied
+        unsafe { handle.get_directory_path(file_path.as_ref()) }.map(|s| to_os_string(s).into())
+    })
+}
+
+/// Get previous directory path for a given path
+///
+/// # Panics
+/// - [`IntoCStr::into_cstr()`] fails for `dir_path`
+/// - [`low::GetPrevDirectoryPathHandle`] was obtained elsewhere
+pub fn get_prev_directory_path(dir_path: impl IntoCStr) -> Option<PathBuf> {
+    let dir_path = dir_path.into_cstr().unwrap();
+    with_handle(&PREV_DIRECTORY_PATH, "GetPrevDirectoryPathHandle", |handle| {
+        // SAFETY: The handle's lock is held until the buffer has been copied
+        unsafe { handle.get_prev_directory_path(dir_path.as_ref()) }.map(|s| to_os_string(s).into())
+    })
+}
+
+/// Get current working directory
+///
+/// # Panics
+/// - [`low::GetWorkingDirectoryHandle`] was obtained elsewhere
+pub fn get_working_directory() -> Option<PathBuf> {
+    with_handle(&WORKING_DIRECTORY, "GetWorkingDirectoryHandle", |handle| {
+        // SAFETY: The handle's lock is held until the buffer has been copied
+        unsafe { handle.get_working_directory() }.map(|s| to_os_string(s).into())
+    })
+}
+
+/// Get the directory of the running application
+///
+/// # Panics
+/// - [`low::GetApplicationDirectoryHandle`] was obtained elsewhere
+pub fn get_application_directory() -> Option<PathBuf> {
+    with_handle(&APPLICATION_DIRECTORY, "GetApplicationDirectoryHandle", |handle| {
+        // SAFETY: The handle's lock is held until the buffer has been copied
+        unsafe { handle.get_application_directory() }.map(|s| to_os_string(s).into())
+    })
+}
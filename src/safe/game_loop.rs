@@ -0,0 +1,146 @@
+//! Fixed-timestep updates with interpolated rendering
+//!
+//! ```ignore
+//! let mut game_loop = GameLoop::new(Duration::from_secs(1) / 60);
+//! game_loop.run(
+//!     &mut rl,
+//!     |rl, dt| world.update(rl, dt),
+//!     |_rl, d, _m, alpha| world.draw(d, alpha),
+//! );
+//! ```
+
+use super::*;
+
+/// Default longest frame time accounted for, see [`GameLoop::with_max_frame_time()`]
+const DEFAULT_MAX_FRAME_TIME: Duration = Duration::from_millis(250);
+
+/// Updates due in one frame, see [`GameLoop::advance()`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Ticks {
+    /// Number of fixed steps to simulate
+    pub updates: u32,
+    /// How far between the last and the next update the frame lies, in `0.0..1.0`
+    pub alpha: f32,
+}
+
+/// Timing statistics of a [`GameLoop`]
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct LoopStats {
+    /// Frames advanced so far
+    pub frames: u64,
+    /// Fixed updates run so far
+    pub updates: u64,
+    /// Duration of the last frame, before clamping
+    pub frame_time: Duration,
+    /// Fixed updates run in the last frame
+    pub frame_updates: u32,
+    /// Frames that were longer than the maximum frame time
+    pub clamped_frames: u64,
+    /// Total time dropped by clamping, such as while paused in a debugger
+    pub dropped_time: Duration,
+    /// Time since the window was opened, as of the last frame
+    pub elapsed: Duration,
+}
+
+/// Runs updates at a fixed rate and renders once per frame
+#[derive(Debug, Clone, PartialEq)]
+pub struct GameLoop {
+    step: Duration,
+    max_frame_time: Duration,
+    accumulator: Duration,
+    stats: LoopStats,
+}
+
+impl GameLoop {
+    /// Update every `step`, accounting for at most 250ms per frame
+    ///
+    /// # Panics
+    /// - `step` is zero
+    #[inline]
+    pub fn new(step: Duration) -> Self {
+        assert!(!step.is_zero(), "step should be greater than zero");
+        Self {
+            step,
+            max_frame_time: DEFAULT_MAX_FRAME_TIME,
+            accumulator: Duration::ZERO,
+            stats: LoopStats::default(),
+        }
+    }
+
+    /// Set the longest frame time accounted for
+    ///
+    /// Longer frames, such as after a debugger pause or while the window is dragged, are shortened to this,
+    /// so the simulation slows down instead of running a burst of updates to catch up.
+    #[inline]
+    pub fn with_max_frame_time(mut self, max_frame_time: Duration) -> Self {
+        self.max_frame_time = max_frame_time;
+        self
+    }
+
+    /// Time simulated by each update
+    #[inline]
+    pub const fn step(&self) -> Duration {
+        self.step
+    }
+
+    /// Timing statistics
+    #[inline]
+    pub const fn stats(&self) -> &LoopStats {
+        &self.stats
+    }
+
+    /// Account for a frame that took `frame_time`, returning the updates it is due
+    pub fn advance(&mut self, frame_time: Duration) -> Ticks {
+        let clamped = frame_time.min(self.max_frame_time);
+        if clamped < frame_time {
+            self.stats.clamped_frames += 1;
+            self.stats.dropped_time += frame_time - clamped;
+        }
+        self.accumulator += clamped;
+        let mut updates = 0;
+        while self.accumulator >= self.step {
+            self.accumulator -= self.step;
+            updates += 1;
+        }
+        self.stats.frames += 1;
+        self.stats.updates += u64::from(updates);
+        self.stats.frame_time = frame_time;
+        self.stats.frame_updates = updates;
+        Ticks {
+            updates,
+            alpha: self.accumulator.as_secs_f32() / self.step.as_secs_f32(),
+        }
+    }
+
+    /// Run one frame: the due updates, then `render` inside [`Window::draw()`]
+    ///
+    /// Frame time is Raylib's [`WindowInner::get_frame_duration()`], the duration of the previous frame.
+    pub fn frame<'ctx>(
+        &mut self,
+        window: &mut Window<'ctx>,
+        mut update: impl FnMut(&mut WindowInner<'ctx>, Duration),
+        render: impl FnOnce(&mut WindowInner<'ctx>, &mut Drawing, &mut BaseDrawMode, f32),
+    ) {
+        let ticks = self.advance(window.get_frame_duration());
+        self.stats.elapsed = window.get_elapsed();
+        for _ in 0..ticks.updates {
+            update(window, self.step);
+        }
+        window.draw(|rl, d, m| render(rl, d, m, ticks.alpha));
+    }
+
+    /// Run frames until the window should close
+    ///
+    /// `update` is called with [`GameLoop::step()`] at a fixed rate, `render` once per frame with the
+    /// interpolation factor between the previous and the next update.
+    pub fn run<'ctx>(
+        &mut self,
+        window: &mut Window<'ctx>,
+        mut update: impl FnMut(&mut WindowInner<'ctx>, Duration),
+        mut render: impl FnMut(&mut WindowInner<'ctx>, &mut Drawing, &mut BaseDrawMode, f32),
+    ) {
+        while !window.should_close() {
+            self.frame(window, &mut update, &mut render);
+        }
+    }
+}
@@ -0,0 +1,224 @@
+//! Gamepads as handles, and connection events
+//!
+//! [`WindowInner::gamepad()`] only returns a [`Gamepad`] while it is connected, and the handle borrows the
+//! window, so it can't outlive the frame in which its availability was checked.
+//! [`Gamepads`] tracks connections across frames:
+//!
+//! ```ignore
+//! let mut gamepads = Gamepads::new();
+//! while !rl.should_close() {
+//!     for event in gamepads.update(&rl) {
+//!         match event {
+//!             GamepadEvent::Connected(pad) => println!("player {} joined with {:?}", pad.index, pad.name),
+//!             GamepadEvent::Disconnected(pad) => println!("player {} left", pad.index),
+//!         }
+//!     }
+//!     if let Some(mut pad) = rl.gamepad(0) {
+//!         if pad.is_button_pressed(GamepadButton::RightFaceDown) {
+//!             pad.set_vibration(0.5, 0.5, Duration::from_millis(100));
+//!         }
+//!     }
+//!     // ...
+//! }
+//! ```
+
+use super::*;
+
+/// A connected gamepad, see [`WindowInner::gamepad()`]
+pub struct Gamepad<'w> {
+    index: usize,
+    _window: PhantomData<&'w mut WindowContext>,
+}
+
+impl Gamepad<'_> {
+    /// Gamepad slot, below [`MAX_GAMEPADS`]
+    #[inline]
+    pub const fn index(&self) -> usize {
+        self.index
+    }
+
+    /// Get internal name id
+    ///
+    /// # Panics
+    /// - Raylib returns a string that isn't valid utf8
+    #[inline]
+    pub fn name(&self) -> Option<&str> {
+        // SAFETY: `Gamepad` borrows the window, proving Raylib is initialized
+        unsafe {
+            low::get_gamepad_name(self.index)
+                .map(|s| str::from_utf8(s.to_bytes()).unwrap())
+        }
+    }
+
+    /// Check if a button has been pressed once
+    #[inline]
+    pub fn is_button_pressed(&self, button: GamepadButton) -> bool {
+        // SAFETY: `Gamepad` borrows the window, proving Raylib is initialized
+        unsafe { low::is_gamepad_button_pressed(self.index, button.into()) }
+    }
+
+    /// Check if a button is being pressed
+    #[inline]
+    pub fn is_button_down(&self, button: GamepadButton) -> bool {
+        // SAFETY: `Gamepad` borrows the window, proving Raylib is initialized
+        unsafe { low::is_gamepad_button_down(self.index, button.into()) }
+    }
+
+    /// Check if a button has been released once
+    #[inline]
+    pub fn is_button_released(&self, button: GamepadButton) -> bool {
+        // SAFETY: `Gamepad` borrows the window, proving Raylib is initialized
+        unsafe { low::is_gamepad_button_released(self.index, button.into()) }
+    }
+
+    /// Check if a button is NOT being pressed
+    #[inline]
+    pub fn is_button_up(&self, button: GamepadButton) -> bool {
+        // SAFETY: `Gamepad` borrows the window, proving Raylib is initialized
+        unsafe { low::is_gamepad_button_up(self.index, button.into()) }
+    }
+
+    /// Get axis count
+    #[inline]
+    pub fn axis_count(&self) -> usize {
+        // SAFETY: `Gamepad` borrows the window, proving Raylib is initialized
+        unsafe { low::get_gamepad_axis_count(self.index) }
+    }
+
+    /// Get movement value for an axis
+    #[inline]
+    pub fn axis_movement(&self, axis: GamepadAxis) -> f32 {
+        // SAFETY: `Gamepad` borrows the window, proving Raylib is initialized
+        unsafe { low::get_gamepad_axis_movement(self.index, axis.into()) }
+    }
+
+    /// Set vibration for both motors, in `0.0..=1.0`
+    #[inline]
+    pub fn set_vibration(&mut self, left_motor: f32, right_motor: f32, duration: Duration) {
+        // SAFETY: `Gamepad` borrows the window, proving Raylib is initialized
+        unsafe {
+            low::set_gamepad_vibration(self.index, left_motor, right_motor, duration.as_secs_f32());
+        }
+    }
+}
+
+impl WindowInner<'_> {
+    /// Get a gamepad, [`None`] if it is not connected
+    #[inline]
+    pub fn gamepad(&mut self, gamepad: usize) -> Option<Gamepad<'_>> {
+        (gamepad < MAX_GAMEPADS && self.is_gamepad_available(gamepad)).then_some(Gamepad {
+            index: gamepad,
+            _window: PhantomData,
+        })
+    }
+}
+
+/// A gamepad seen by [`Gamepads`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GamepadInfo {
+    /// Gamepad slot, below [`MAX_GAMEPADS`]
+    pub index: usize,
+    /// Internal name id, as of when it connected
+    pub name: Option<String>,
+}
+
+/// A change reported by [`Gamepads::update()`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GamepadEvent {
+    /// A gamepad became available
+    Connected(GamepadInfo),
+    /// A gamepad is no longer available
+    Disconnected(GamepadInfo),
+}
+
+/// Gamepad connections, tracked by comparing availability between frames
+#[derive(Debug, Clone, Default)]
+pub struct Gamepads {
+    slots: [Option<GamepadInfo>; MAX_GAMEPADS],
+}
+
+impl Gamepads {
+    /// No gamepads connected yet; the first update reports every connected gamepad
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Check availability of every gamepad and report what changed since the last update; call once per frame
+    ///
+    /// A gamepad whose name differs from the last update is reported as replaced:
+    /// disconnected, then connected.
+    pub fn update(&mut self, rl: &WindowInner<'_>) -> Vec<GamepadEvent> {
+        let mut current = self.slots.clone();
+        for (index, slot) in current.iter_mut().enumerate() {
+            if !rl.is_gamepad_available(index) {
+                *slot = None;
+                continue;
+            }
+            let name = rl.get_gamepad_name(index);
+            if slot.as_ref().is_none_or(|info| info.name.as_deref() != name) {
+                *slot = Some(GamepadInfo {
+                    index,
+                    name: name.map(str::to_owned),
+                });
+            }
+        }
+        self.apply(current)
+    }
+
+    /// Report what changed since the last update according to an input snapshot
+    ///
+    /// A gamepad whose name differs from the last update is reported as replaced:
+    /// disconnected, then connected.
+    pub fn update_from(&mut self, input: &InputState) -> Vec<GamepadEvent> {
+        let mut current: [Option<GamepadInfo>; MAX_GAMEPADS] = Default::default();
+        for gamepad in input.gamepads.iter().filter(|gamepad| gamepad.index < MAX_GAMEPADS) {
+            current[gamepad.index] = Some(GamepadInfo {
+                index: gamepad.index,
+                name: gamepad.name.clone(),
+            });
+        }
+        self.apply(current)
+    }
+
+    fn apply(&mut self, current: [Option<GamepadInfo>; MAX_GAMEPADS]) -> Vec<GamepadEvent> {
+        let mut events = Vec::new();
+        for (slot, current) in self.slots.iter_mut().zip(current) {
+            if *slot == current {
+                continue;
+            }
+            if let Some(previous) = slot.take() {
+                events.push(GamepadEvent::Disconnected(previous));
+            }
+            if let Some(current) = current {
+                events.push(GamepadEvent::Connected(current.clone()));
+                *slot = Some(current);
+            }
+        }
+        events
+    }
+
+    /// Check if a gamepad was connected as of the last update
+    #[inline]
+    pub fn is_connected(&self, gamepad: usize) -> bool {
+        self.get(gamepad).is_some()
+    }
+
+    /// A gamepad connected as of the last update
+    #[inline]
+    pub fn get(&self, gamepad: usize) -> Option<&GamepadInfo> {
+        self.slots.get(gamepad)?.as_ref()
+    }
+
+    /// Name of a gamepad connected as of the last update
+    #[inline]
+    pub fn name(&self, gamepad: usize) -> Option<&str> {
+        self.get(gamepad)?.name.as_deref()
+    }
+
+    /// Every gamepad connected as of the last update, by ascending index
+    #[inline]
+    pub fn iter(&self) -> impl Iterator<Item = &GamepadInfo> {
+        self.slots.iter().flatten()
+    }
+}
@@ -0,0 +1,281 @@
+//! SDL gamepad mapping databases
+//!
+//! Loads a `gamecontrollerdb.txt` (<https://github.com/mdqinc/SDL_GameControllerDB>), keeping the mappings for
+//! the current platform and reporting lines that can't be used instead of failing on the first one:
+//!
+//! ```ignore
+//! let report = rl.load_gamepad_mappings(Path::new("assets/gamecontrollerdb.txt"))?;
+//! for error in &report.errors {
+//!     eprintln!("gamecontrollerdb.txt: {error}");
+//! }
+//! println!("{} gamepad mappings loaded", report.accepted);
+//! ```
+
+use std::{ffi::CString, fmt};
+use super::*;
+use into_cstr::IntoCStrNulError;
+
+/// Why a line of a mapping database was skipped
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GamepadMappingError {
+    /// The line is not valid utf8
+    InvalidUtf8 {
+        /// 1-based line number
+        line: usize,
+    },
+    /// The line contains a nul byte
+    ContainsNul {
+        /// 1-based line number
+        line: usize,
+    },
+    /// The line does not start with a 32 digit hexadecimal GUID
+    InvalidGuid {
+        /// 1-based line number
+        line: usize,
+    },
+    /// The GUID is not followed by a name
+    MissingName {
+        /// 1-based line number
+        line: usize,
+    },
+    /// A field is not a `key:value` pair
+    InvalidField {
+        /// 1-based line number
+        line: usize,
+        /// The offending field
+        field: String,
+    },
+    /// Raylib refused the mapping
+    ///
+    /// Only reported by platforms that signal failure with a negative result (SDL).
+    Rejected {
+        /// 1-based line number
+        line: usize,
+    },
+}
+
+impl GamepadMappingError {
+    /// 1-based line number of the skipped line
+    #[inline]
+    pub const fn line(&self) -> usize {
+        match self {
+            Self::InvalidUtf8 { line }
+            | Self::ContainsNul { line }
+            | Self::InvalidGuid { line }
+            | Self::MissingName { line }
+            | Self::InvalidField { line, .. }
+            | Self::Rejected { line } => *line,
+        }
+    }
+}
+
+impl fmt::Display for GamepadMappingError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidUtf8 { line } => write!(f, "line {line}: invalid utf8"),
+            Self::ContainsNul { line } => write!(f, "line {line}: contains a nul byte"),
+            Self::InvalidGuid { line } => write!(f, "line {line}: expected a 32 digit hexadecimal GUID"),
+            Self::MissingName { line } => write!(f, "line {line}: missing gamepad name"),
+            Self::InvalidField { line, field } => write!(f, "line {line}: expected `key:value`, found `{field}`"),
+            Self::Rejected { line } => write!(f, "line {line}: mapping rejected by Raylib"),
+        }
+    }
+}
+
+impl std::error::Error for GamepadMappingError {}
+
+/// An error from [`WindowInner::load_gamepad_mappings()`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GamepadMappingsLoadError {
+    /// The file name contained a nul byte
+    InvalidPath(IntoCStrNulError),
+    /// Raylib could not read the file
+    LoadFailed,
+}
+
+impl From<IntoCStrNulError> for GamepadMappingsLoadError {
+    #[inline]
+    fn from(value: IntoCStrNulError) -> Self {
+        Self::InvalidPath(value)
+    }
+}
+
+impl fmt::Display for GamepadMappingsLoadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidPath(e) => write!(f, "invalid path: {e}"),
+            Self::LoadFailed => f.write_str("failed to load gamepad mappings"),
+        }
+    }
+}
+
+impl std::error::Error for GamepadMappingsLoadError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::InvalidPath(e) => Some(e),
+            Self::LoadFailed => None,
+        }
+    }
+}
+
+/// The mappings of a database that apply to one platform
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct GamepadMappings {
+    mappings: Vec<(usize, CString)>,
+    other_platforms: usize,
+    errors: Vec<GamepadMappingError>,
+}
+
+impl GamepadMappings {
+    /// The `platform:` name SDL uses for the target platform, [`None`] if it has none
+    pub const CURRENT_PLATFORM: Option<&'static str> =
+        if cfg!(target_os = "windows") { Some("Windows") }
+        else if cfg!(target_os = "macos") { Some("Mac OS X") }
+        else if cfg!(target_os = "android") { Some("Android") }
+        else if cfg!(target_os = "ios") { Some("iOS") }
+        else if cfg!(target_os = "linux") { Some("Linux") }
+        else { None };
+
+    /// Parse a database, keeping the mappings for [`Self::CURRENT_PLATFORM`]
+    #[inline]
+    pub fn parse(data: &[u8]) -> Self {
+        Self::parse_for(data, Self::CURRENT_PLATFORM)
+    }
+
+    /// Parse a database, keeping the mappings for `platform` and the mappings without a `platform:` field
+    ///
+    /// Blank lines and lines starting with `#` are skipped.
+    pub fn parse_for(data: &[u8], platform: Option<&str>) -> Self {
+        let mut parsed = Self::default();
+        for (index, line) in data.split(|&b| b == b'\n').enumerate() {
+            let number = index + 1;
+            let Ok(line) = str::from_utf8(line) else {
+                parsed.errors.push(GamepadMappingError::InvalidUtf8 { line: number });
+                continue;
+            };
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            match Self::parse_line(line, number) {
+                Err(error) => parsed.errors.push(error),
+                Ok(Some(line_platform)) if Some(line_platform) != platform => parsed.other_platforms += 1,
+                Ok(_) => match CString::new(line) {
+                    Ok(mapping) => parsed.mappings.push((number, mapping)),
+                    Err(_) => parsed.errors.push(GamepadMappingError::ContainsNul { line: number }),
+                },
+            }
+        }
+        parsed
+    }
+
+    /// Validate a mapping and return its platform
+    fn parse_line(line: &str, number: usize) -> Result<Option<&str>, GamepadMappingError> {
+        let mut fields = line.split(',');
+        let guid = fields.next().unwrap_or_default();
+        if guid.len() != 32 || !guid.bytes().all(|b| b.is_ascii_hexdigit()) {
+            return Err(GamepadMappingError::InvalidGuid { line: number });
+        }
+        if fields.next().is_none_or(|name| name.trim().is_empty()) {
+            return Err(GamepadMappingError::MissingName { line: number });
+        }
+        let mut platform = None;
+        // Lines usually end with a comma
+        for field in fields.filter(|field| !field.is_empty()) {
+            match field.split_once(':') {
+                Some(("platform", name)) => platform = Some(name),
+                Some((key, value)) if !key.is_empty() && !value.is_empty() => {}
+                _ => return Err(GamepadMappingError::InvalidField { line: number, field: field.to_owned() }),
+            }
+        }
+        Ok(platform)
+    }
+
+    /// Number of mappings kept
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.mappings.len()
+    }
+
+    /// Check if no mappings were kept
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.mappings.is_empty()
+    }
+
+    /// The mappings kept, with their 1-based line numbers
+    #[inline]
+    pub fn iter(&self) -> impl Iterator<Item = (usize, &str)> {
+        // Always utf8, the mappings were parsed from `&str`
+        self.mappings.iter().map(|(line, mapping)| (*line, mapping.to_str().unwrap_or_default()))
+    }
+
+    /// Number of valid mappings skipped for being for another platform
+    #[inline]
+    pub const fn other_platforms(&self) -> usize {
+        self.other_platforms
+    }
+
+    /// Lines that could not be parsed
+    #[inline]
+    pub fn errors(&self) -> &[GamepadMappingError] {
+        &self.errors
+    }
+}
+
+/// The result of applying [`GamepadMappings`]
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct GamepadMappingsReport {
+    /// Number of mappings applied
+    pub accepted: usize,
+    /// Number of valid mappings skipped for being for another platform
+    pub other_platforms: usize,
+    /// Lines that were skipped, in order
+    pub errors: Vec<GamepadMappingError>,
+}
+
+impl WindowInner<'_> {
+    /// Load a mapping database file (`gamecontrollerdb.txt`) and apply the mappings for this platform
+    ///
+    /// Invalid lines are skipped and reported rather than failing the whole file.
+    pub fn load_gamepad_mappings(&mut self, file_name: impl IntoCStr) -> Result<GamepadMappingsReport, GamepadMappingsLoadError> {
+        let file_name = file_name.into_cstr()?;
+        // SAFETY: Existence of `WindowInner` proves Raylib is initialized
+        let text = unsafe { low::load_file_text(file_name.as_ref()) }
+            .ok_or(GamepadMappingsLoadError::LoadFailed)?;
+        let mappings = GamepadMappings::parse(text.to_bytes());
+        // SAFETY: `text` was loaded by `load_file_text` and is not used after this
+        unsafe {
+            low::unload_file_text(text);
+        }
+        Ok(self.apply_gamepad_mappings(&mappings))
+    }
+
+    /// Apply the mappings for this platform from the contents of a mapping database
+    ///
+    /// Invalid lines are skipped and reported rather than failing the whole database.
+    #[inline]
+    pub fn load_gamepad_mappings_from_memory(&mut self, data: &[u8]) -> GamepadMappingsReport {
+        self.apply_gamepad_mappings(&GamepadMappings::parse(data))
+    }
+
+    /// Apply parsed mappings, one at a time
+    pub fn apply_gamepad_mappings(&mut self, mappings: &GamepadMappings) -> GamepadMappingsReport {
+        let mut report = GamepadMappingsReport {
+            accepted: 0,
+            other_platforms: mappings.other_platforms,
+            errors: mappings.errors.clone(),
+        };
+        for (line, mapping) in &mappings.mappings {
+            // SAFETY: Existence of `WindowInner` proves Raylib is initialized
+            if unsafe { low::set_gamepad_mappings(Some(mapping)) } < 0 {
+                report.errors.push(GamepadMappingError::Rejected { line: *line });
+            } else {
+                report.accepted += 1;
+            }
+        }
+        // Keep the errors in line order
+        report.errors.sort_by_key(GamepadMappingError::line);
+        report
+    }
+}
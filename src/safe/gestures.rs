@@ -0,0 +1,207 @@
+//! Touch gestures for one frame, and synthetic touches
+//!
+//! [`WindowInner::gestures()`] reads what Raylib's gesture system detected this frame:
+//!
+//! ```ignore
+//! rl.set_gestures_enabled(Gesture::Tap | Gesture::Drag | Gesture::PinchIn | Gesture::PinchOut);
+//! let gestures = rl.gestures();
+//! if let Some(pinch) = gestures.pinch() {
+//!     camera.zoom *= 1.0 + pinch.vector.y * 0.01;
+//! }
+//! ```
+//!
+//! Raylib also recognizes the mouse as a single touch point. To test multi-touch on desktop, feed [`GestureEvent`]s
+//! through [`WindowInner::process_gesture_event()`]; they are recognized on the next frame.
+
+use super::*;
+
+/// A touch action with the touch points involved, see [`WindowInner::process_gesture_event()`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct GestureEvent {
+    /// What happened
+    pub action: TouchAction,
+    /// Touch points down during the action; only the first [`GestureEvent::MAX_POINTS`] are used
+    pub points: Vec<TouchPoint>,
+}
+
+impl GestureEvent {
+    /// Number of touch points Raylib's gesture system tracks (`MAX_TOUCH_POINTS` in `rgestures.h`)
+    pub const MAX_POINTS: usize = 8;
+
+    /// An event with any number of touch points
+    #[inline]
+    pub fn new(action: TouchAction, points: impl IntoIterator<Item = TouchPoint>) -> Self {
+        Self {
+            action,
+            points: points.into_iter().collect(),
+        }
+    }
+
+    /// A finger touching the screen
+    #[inline]
+    pub fn down(id: u32, position: Vector2) -> Self {
+        Self::new(TouchAction::Down, [TouchPoint { id, position }])
+    }
+
+    /// A finger moving
+    #[inline]
+    pub fn moved(id: u32, position: Vector2) -> Self {
+        Self::new(TouchAction::Move, [TouchPoint { id, position }])
+    }
+
+    /// A finger being lifted
+    #[inline]
+    pub fn up(id: u32, position: Vector2) -> Self {
+        Self::new(TouchAction::Up, [TouchPoint { id, position }])
+    }
+
+    pub(crate) fn as_sys(&self) -> sys::GestureEvent {
+        let mut event = sys::GestureEvent {
+            touchAction: self.action as i32,
+            ..Default::default()
+        };
+        let points = self.points.iter()
+            .zip(event.pointId.iter_mut().zip(&mut event.position));
+        let mut count = 0;
+        for (point, (id, position)) in points {
+            *id = point.id as _;
+            *position = point.position.into();
+            count += 1;
+        }
+        event.pointCount = count;
+        event
+    }
+}
+
+/// Direction of a swipe
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SwipeDirection {
+    /// Towards positive X
+    Right,
+    /// Towards negative X
+    Left,
+    /// Towards negative Y
+    Up,
+    /// Towards positive Y
+    Down,
+}
+
+/// A detected swipe, see [`GestureState::swipe()`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Swipe {
+    /// Closest direction
+    pub direction: SwipeDirection,
+    /// Angle in degrees, horizontal-right is 0, counterclockwise
+    pub angle: f32,
+}
+
+/// A detected pinch, see [`GestureState::pinch()`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Pinch {
+    /// True if the fingers are moving apart
+    pub outward: bool,
+    /// Change in the distance between the two points
+    pub vector: Vector2,
+    /// Angle of the line between the two points in degrees, horizontal-right is 0, counterclockwise
+    pub angle: f32,
+}
+
+/// Gestures detected in one frame, see [`WindowInner::gestures()`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GestureState {
+    /// Latest detected gesture
+    pub detected: Gesture,
+    /// How long the current hold has lasted
+    pub hold_duration: Duration,
+    /// Vector from the initial touch point to the current one while dragging
+    pub drag_vector: Vector2,
+    /// Drag angle in degrees, horizontal-right is 0, counterclockwise
+    pub drag_angle: f32,
+    /// Change in the distance between the two points while pinching
+    pub pinch_vector: Vector2,
+    /// Pinch angle in degrees, horizontal-right is 0, counterclockwise
+    pub pinch_angle: f32,
+}
+
+impl Default for GestureState {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            detected: Gesture::None,
+            hold_duration: Duration::ZERO,
+            drag_vector: Vector2::default(),
+            drag_angle: 0.0,
+            pinch_vector: Vector2::default(),
+            pinch_angle: 0.0,
+        }
+    }
+}
+
+impl GestureState {
+    /// Check if any of `gesture` was detected
+    #[inline]
+    pub fn is_detected(&self, gesture: Gesture) -> bool {
+        self.detected.intersects(gesture)
+    }
+
+    /// Check if the screen was tapped
+    #[inline]
+    pub fn tap(&self) -> bool {
+        self.is_detected(Gesture::Tap)
+    }
+
+    /// Check if the screen was tapped twice in quick succession
+    #[inline]
+    pub fn double_tap(&self) -> bool {
+        self.is_detected(Gesture::Doubletap)
+    }
+
+    /// How long a finger has been held still, if it is
+    #[inline]
+    pub fn hold(&self) -> Option<Duration> {
+        self.is_detected(Gesture::Hold).then_some(self.hold_duration)
+    }
+
+    /// Vector from where the drag started, if dragging
+    #[inline]
+    pub fn drag(&self) -> Option<Vector2> {
+        self.is_detected(Gesture::Drag).then_some(self.drag_vector)
+    }
+
+    /// The swipe that just ended, if any
+    pub fn swipe(&self) -> Option<Swipe> {
+        let direction = [
+            (Gesture::SwipeRight, SwipeDirection::Right),
+            (Gesture::SwipeLeft, SwipeDirection::Left),
+            (Gesture::SwipeUp, SwipeDirection::Up),
+            (Gesture::SwipeDown, SwipeDirection::Down),
+        ].into_iter().find_map(|(gesture, direction)| self.is_detected(gesture).then_some(direction))?;
+        Some(Swipe { direction, angle: self.drag_angle })
+    }
+
+    /// The pinch in progress, if any
+    pub fn pinch(&self) -> Option<Pinch> {
+        let outward = if self.is_detected(Gesture::PinchOut) {
+            true
+        } else if self.is_detected(Gesture::PinchIn) {
+            false
+        } else {
+            return None;
+        };
+        Some(Pinch { outward, vector: self.pinch_vector, angle: self.pinch_angle })
+    }
+}
+
+impl WindowInner<'_> {
+    /// Read the gestures detected this frame
+    pub fn gestures(&self) -> GestureState {
+        GestureState {
+            detected: self.get_gesture_detected(),
+            hold_duration: self.get_gesture_hold_duration(),
+            drag_vector: self.get_gesture_drag_vector(),
+            drag_angle: self.get_gesture_drag_angle(),
+            pinch_vector: self.get_gesture_pinch_vector(),
+            pinch_angle: self.get_gesture_pinch_angle(),
+        }
+    }
+}
@@ -0,0 +1,331 @@
+//! Input for one frame as a plain value
+//!
+//! [`WindowInner::input()`] reads everything at once, so game systems can take an [`InputState`]
+//! instead of the window, and tests can build one by hand:
+//!
+//! ```ignore
+//! let mut input = InputState::default();
+//! input.keys.down.push(KeyboardKey::Right);
+//! player.update(&input, step);
+//! assert!(player.velocity.x > 0.0);
+//! ```
+
+use super::*;
+
+/// Number of gamepad slots Raylib tracks (`MAX_GAMEPADS` in `config.h`)
+pub const MAX_GAMEPADS: usize = 4;
+
+/// Which buttons of a device are held, were pressed or were released this frame
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ButtonStates<B> {
+    /// Buttons being held
+    pub down: Vec<B>,
+    /// Buttons pressed this frame
+    pub pressed: Vec<B>,
+    /// Buttons released this frame
+    pub released: Vec<B>,
+}
+
+impl<B> Default for ButtonStates<B> {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            down: Vec::new(),
+            pressed: Vec::new(),
+            released: Vec::new(),
+        }
+    }
+}
+
+impl<B: Copy + PartialEq> ButtonStates<B> {
+    /// Read the state of each of `buttons`
+    fn read(buttons: &[B], is_down: impl Fn(B) -> bool, is_pressed: impl Fn(B) -> bool, is_released: impl Fn(B) -> bool) -> Self {
+        Self {
+            down: buttons.iter().copied().filter(|&button| is_down(button)).collect(),
+            pressed: buttons.iter().copied().filter(|&button| is_pressed(button)).collect(),
+            released: buttons.iter().copied().filter(|&button| is_released(button)).collect(),
+        }
+    }
+
+    /// Check if a button is being held
+    #[inline]
+    pub fn is_down(&self, button: B) -> bool {
+        self.down.contains(&button)
+    }
+
+    /// Check if a button was pressed this frame
+    #[inline]
+    pub fn is_pressed(&self, button: B) -> bool {
+        self.pressed.contains(&button)
+    }
+
+    /// Check if a button was released this frame
+    #[inline]
+    pub fn is_released(&self, button: B) -> bool {
+        self.released.contains(&button)
+    }
+
+    /// Check if a button is NOT being held
+    #[inline]
+    pub fn is_up(&self, button: B) -> bool {
+        !self.is_down(button)
+    }
+}
+
+/// Mouse state for one frame
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct MouseState {
+    /// Position, after the mouse offset and scale
+    pub position: Vector2,
+    /// Movement since the previous frame
+    pub delta: Vector2,
+    /// Wheel movement on both axes
+    pub wheel: Vector2,
+    /// Button states
+    pub buttons: ButtonStates<MouseButton>,
+}
+
+/// A touch point for one frame
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct TouchPoint {
+    /// Identifier that stays the same while the finger is down
+    pub id: u32,
+    /// Position relative to the screen
+    pub position: Vector2,
+}
+
+/// State of a connected gamepad for one frame
+#[derive(Debug, Clone, PartialEq)]
+pub struct GamepadState {
+    /// Gamepad slot, below [`MAX_GAMEPADS`]
+    pub index: usize,
+    /// Internal name id
+    pub name: Option<String>,
+    /// Button states
+    pub buttons: ButtonStates<GamepadButton>,
+    /// Movement of each axis, indexed by [`GamepadAxis`]
+    pub axes: [f32; GamepadAxis::ALL.len()],
+}
+
+impl GamepadState {
+    /// A connected gamepad with nothing held and every axis at rest
+    ///
+    /// Sticks rest at `0.0` and triggers at `-1.0`.
+    #[inline]
+    pub fn new(index: usize) -> Self {
+        let mut axes = [0.0; GamepadAxis::ALL.len()];
+        axes[GamepadAxis::LeftTrigger as usize] = -1.0;
+        axes[GamepadAxis::RightTrigger as usize] = -1.0;
+        Self {
+            index,
+            name: None,
+            buttons: ButtonStates::default(),
+            axes,
+        }
+    }
+
+    /// Get movement value for an axis
+    #[inline]
+    pub const fn axis(&self, axis: GamepadAxis) -> f32 {
+        self.axes[axis as usize]
+    }
+
+    /// Set movement value for an axis
+    #[inline]
+    pub const fn set_axis(&mut self, axis: GamepadAxis, value: f32) {
+        self.axes[axis as usize] = value;
+    }
+}
+
+/// Snapshot of all input for one frame, see [`WindowInner::input()`]
+///
+/// The query methods mirror the ones on [`WindowInner`] so code can switch between them easily.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct InputState {
+    /// Key states
+    pub keys: ButtonStates<KeyboardKey>,
+    /// Keys pressed again by key repeat this frame
+    pub repeated_keys: Vec<KeyboardKey>,
+    /// Characters typed this frame, in order
+    pub chars: Vec<char>,
+    /// Mouse state
+    pub mouse: MouseState,
+    /// Touch points, in Raylib's order
+    pub touches: Vec<TouchPoint>,
+    /// Connected gamepads, by ascending index
+    pub gamepads: Vec<GamepadState>,
+}
+
+impl InputState {
+    /// Check if a key has been pressed once
+    #[inline]
+    pub fn is_key_pressed(&self, key: KeyboardKey) -> bool {
+        self.keys.is_pressed(key)
+    }
+
+    /// Check if a key has been pressed again
+    #[inline]
+    pub fn is_key_pressed_repeat(&self, key: KeyboardKey) -> bool {
+        self.repeated_keys.contains(&key)
+    }
+
+    /// Check if a key is being pressed
+    #[inline]
+    pub fn is_key_down(&self, key: KeyboardKey) -> bool {
+        self.keys.is_down(key)
+    }
+
+    /// Check if a key has been released once
+    #[inline]
+    pub fn is_key_released(&self, key: KeyboardKey) -> bool {
+        self.keys.is_released(key)
+    }
+
+    /// Check if a key is NOT being pressed
+    #[inline]
+    pub fn is_key_up(&self, key: KeyboardKey) -> bool {
+        self.keys.is_up(key)
+    }
+
+    /// Check if a mouse button has been pressed once
+    #[inline]
+    pub fn is_mouse_button_pressed(&self, button: MouseButton) -> bool {
+        self.mouse.buttons.is_pressed(button)
+    }
+
+    /// Check if a mouse button is being pressed
+    #[inline]
+    pub fn is_mouse_button_down(&self, button: MouseButton) -> bool {
+        self.mouse.buttons.is_down(button)
+    }
+
+    /// Check if a mouse button has been released once
+    #[inline]
+    pub fn is_mouse_button_released(&self, button: MouseButton) -> bool {
+        self.mouse.buttons.is_released(button)
+    }
+
+    /// Check if a mouse button is NOT being pressed
+    #[inline]
+    pub fn is_mouse_button_up(&self, button: MouseButton) -> bool {
+        self.mouse.buttons.is_up(button)
+    }
+
+    /// Get mouse position XY
+    #[inline]
+    pub const fn get_mouse_position(&self) -> Vector2 {
+        self.mouse.position
+    }
+
+    /// Get mouse delta between frames
+    #[inline]
+    pub const fn get_mouse_delta(&self) -> Vector2 {
+        self.mouse.delta
+    }
+
+    /// Get mouse wheel movement for X or Y, whichever is larger
+    #[inline]
+    pub fn get_mouse_wheel_move(&self) -> f32 {
+        let Vector2 { x, y } = self.mouse.wheel;
+        if x.abs() > y.abs() { x } else { y }
+    }
+
+    /// Get a gamepad by index, [`None`] if it is not connected
+    #[inline]
+    pub fn gamepad(&self, gamepad: usize) -> Option<&GamepadState> {
+        self.gamepads.iter().find(|state| state.index == gamepad)
+    }
+
+    /// Check if a gamepad is available
+    #[inline]
+    pub fn is_gamepad_available(&self, gamepad: usize) -> bool {
+        self.gamepad(gamepad).is_some()
+    }
+
+    /// Check if a gamepad button has been pressed once
+    #[inline]
+    pub fn is_gamepad_button_pressed(&self, gamepad: usize, button: GamepadButton) -> bool {
+        self.gamepad(gamepad).is_some_and(|state| state.buttons.is_pressed(button))
+    }
+
+    /// Check if a gamepad button is being pressed
+    #[inline]
+    pub fn is_gamepad_button_down(&self, gamepad: usize, button: GamepadButton) -> bool {
+        self.gamepad(gamepad).is_some_and(|state| state.buttons.is_down(button))
+    }
+
+    /// Check if a gamepad button has been released once
+    #[inline]
+    pub fn is_gamepad_button_released(&self, gamepad: usize, button: GamepadButton) -> bool {
+        self.gamepad(gamepad).is_some_and(|state| state.buttons.is_released(button))
+    }
+
+    /// Check if a gamepad button is NOT being pressed
+    #[inline]
+    pub fn is_gamepad_button_up(&self, gamepad: usize, button: GamepadButton) -> bool {
+        !self.is_gamepad_button_down(gamepad, button)
+    }
+
+    /// Get movement value for a gamepad axis, `0.0` if the gamepad is not connected
+    #[inline]
+    pub fn get_gamepad_axis_movement(&self, gamepad: usize, axis: GamepadAxis) -> f32 {
+        self.gamepad(gamepad).map_or(0.0, |state| state.axis(axis))
+    }
+
+    /// Get number of touch points
+    #[inline]
+    pub fn get_touch_point_count(&self) -> usize {
+        self.touches.len()
+    }
+}
+
+impl WindowInner<'_> {
+    /// Take a snapshot of all input for this frame
+    ///
+    /// This drains the character queue, so [`WindowInner::get_char_pressed()`] returns [`None`]
+    /// for the rest of the frame; read [`InputState::chars`] instead.
+    pub fn input(&self) -> InputState {
+        let keys = &KeyboardKey::ALL;
+        InputState {
+            keys: ButtonStates::read(
+                keys,
+                |key| self.is_key_down(key),
+                |key| self.is_key_pressed(key),
+                |key| self.is_key_released(key),
+            ),
+            repeated_keys: keys.iter().copied().filter(|&key| self.is_key_pressed_repeat(key)).collect(),
+            chars: std::iter::from_fn(|| self.get_char_pressed()).collect(),
+            mouse: MouseState {
+                position: self.get_mouse_position(),
+                delta: self.get_mouse_delta(),
+                wheel: self.get_mouse_wheel_move_v(),
+                buttons: ButtonStates::read(
+                    &MouseButton::ALL,
+                    |button| self.is_mouse_button_down(button),
+                    |button| self.is_mouse_button_pressed(button),
+                    |button| self.is_mouse_button_released(button),
+                ),
+            },
+            touches: (0..self.get_touch_point_count())
+                .filter_map(|index| Some(TouchPoint {
+                    id: self.get_touch_point_id(index)?,
+                    position: self.get_touch_position(index),
+                }))
+                .collect(),
+            gamepads: (0..MAX_GAMEPADS)
+                .filter(|&index| self.is_gamepad_available(index))
+                .map(|index| GamepadState {
+                    index,
+                    name: self.get_gamepad_name(index).map(str::to_owned),
+                    buttons: ButtonStates::read(
+                        &GamepadButton::ALL,
+                        |button| self.is_gamepad_button_down(index, button),
+                        |button| self.is_gamepad_button_pressed(index, button),
+                        |button| self.is_gamepad_button_released(index, button),
+                    ),
+                    axes: std::array::from_fn(|axis| self.get_gamepad_axis_movement(index, GamepadAxis::ALL[axis])),
+                })
+                .collect(),
+        }
+    }
+}
@@ -2,8 +2,8 @@
 
 #[cfg(target_endian = "little")]
 use std::mem::MaybeUninit;
-use std::{marker::PhantomData, ptr::NonNull, num::NonZeroU32, time::Duration};
-use crate::low;
+use std::{marker::PhantomData, ptr::NonNull, num::NonZeroU32, sync::{Mutex, PoisonError}, thread::ThreadId, time::Duration};
+use crate::low::{self, types::Gesture};
 use into_cstr::IntoCStr;
 
 mod sys {
@@ -97,6 +97,88 @@ mod sys {
 
 pub mod into_cstr;
 
+pub mod audio;
+pub use audio::*;
+
+pub mod ring_buffer;
+pub use ring_buffer::*;
+
+pub mod analyzer;
+pub use analyzer::*;
+
+pub mod stream_queue;
+pub use stream_queue::*;
+
+pub mod wave;
+pub use wave::*;
+
+pub mod window_builder;
+pub use window_builder::*;
+
+pub mod resources;
+pub use resources::*;
+
+pub mod monitor;
+pub use monitor::*;
+
+pub mod window_geometry;
+pub use window_geometry::*;
+
+pub mod virtual_screen;
+pub use virtual_screen::*;
+
+pub mod event_loop;
+pub use event_loop::*;
+
+pub mod game_loop;
+pub use game_loop::*;
+
+pub mod script;
+pub use script::*;
+
+pub mod input_state;
+pub use input_state::*;
+
+pub mod action_map;
+pub use action_map::*;
+
+pub mod text_input;
+pub use text_input::*;
+pub mod gamepad;
+pub use gamepad::*;
+pub mod gamepad_mappings;
+pub use gamepad_mappings::*;
+pub mod rumble;
+pub use rumble::*;
+pub mod gestures;
+pub use gestures::*;
+pub mod touch_tracker;
+pub use touch_tracker::*;
+pub mod automation;
+pub use automation::*;
+pub mod fs;
+pub use fs::*;
+
+#[cfg(feature = "custom_frame_control")]
+pub mod frame_control;
+#[cfg(feature = "custom_frame_control")]
+pub use frame_control::*;
+
+/// An error from parsing a name that is not a variant of a Raylib enum
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ParseEnumError {
+    /// Name of the enum, such as `"KeyboardKey"`
+    pub enum_name: &'static str,
+}
+
+impl std::fmt::Display for ParseEnumError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "unknown {} name", self.enum_name)
+    }
+}
+
+impl std::error::Error for ParseEnumError {}
+
 trait SizedPrimitive<const N: usize> { type Type; }
 impl SizedPrimitive<0> for () { type Type = (); }
 impl SizedPrimitive<1> for () { type Type = i8; }
@@ -105,9 +187,51 @@ impl SizedPrimitive<4> for () { type Type = i32; }
 impl SizedPrimitive<8> for () { type Type = i64; }
 
 macro_rules! sys_enum_wrapper {
+    (@names $Enum:ident [$($prefix:literal)?] $($Variant:ident)*) => {
+        impl $Enum {
+            /// Every variant, in declaration order
+            pub const ALL: [Self; [$(stringify!($Variant)),*].len()] = [$(Self::$Variant),*];
+
+            /// Prefix of every name, so that names of different enums don't overlap
+            const NAME_PREFIX: &str = concat!("" $(, $prefix)?);
+        }
+
+        impl std::fmt::Display for $Enum {
+            /// Write the variant's stable name, which [`FromStr`](std::str::FromStr) accepts back
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                f.write_str(Self::NAME_PREFIX)?;
+                f.write_str(match self {
+                    $(Self::$Variant => stringify!($Variant),)*
+                })
+            }
+        }
+
+        impl std::str::FromStr for $Enum {
+            type Err = ParseEnumError;
+
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                match s.strip_prefix(Self::NAME_PREFIX) {
+                    $(Some(stringify!($Variant)) => Ok(Self::$Variant),)*
+                    _ => Err(ParseEnumError { enum_name: stringify!($Enum) }),
+                }
+            }
+        }
+
+        impl TryFrom<i32> for $Enum {
+            type Error = ();
+
+            #[inline]
+            fn try_from(value: i32) -> Result<Self, Self::Error> {
+                Self::ALL.into_iter()
+                    .find(|&variant| variant as i32 == value)
+                    .ok_or(())
+            }
+        }
+    };
+
     (
         $(#[$m:meta])*
-        $vis:vis enum $Enum:ident {
+        $vis:vis enum $Enum:ident $(as $prefix:literal)? {
             _ := $SysNone:ident,
             $(
                 $(#[$vm:meta])*
@@ -123,6 +247,8 @@ macro_rules! sys_enum_wrapper {
             )*
         }
 
+        sys_enum_wrapper!(@names $Enum [$($prefix)?] $($Variant)*);
+
         impl $Enum {
             #[inline]
             const fn into_sys(self) -> sys::$Enum {
@@ -169,7 +295,7 @@ macro_rules! sys_enum_wrapper {
 
     (
         $(#[$m:meta])*
-        $vis:vis enum $Enum:ident {
+        $vis:vis enum $Enum:ident $(as $prefix:literal)? {
             $(
                 $(#[$vm:meta])*
                 $Variant:ident := $SysVariant:ident
@@ -184,6 +310,8 @@ macro_rules! sys_enum_wrapper {
             )*
         }
 
+        sys_enum_wrapper!(@names $Enum [$($prefix)?] $($Variant)*);
+
         impl $Enum {
             #[inline]
             const fn into_sys(self) -> sys::$Enum {
@@ -219,7 +347,7 @@ macro_rules! sys_enum_wrapper {
 
 /// Vector2, 2 components
 #[repr(C)]
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
 pub struct Vector2 {
     /// Vector x component
     pub x: f32,
@@ -370,6 +498,61 @@ impl<'a> From<&'a Vector4> for &'a sys::Vector4 {
 /// Quaternion, 4 components (Vector4 alias)
 pub type Quaternion = Vector4;
 
+/// Rectangle, 4 components
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Rectangle {
+    /// Rectangle top-left corner position x
+    pub x: f32,
+    /// Rectangle top-left corner position y
+    pub y: f32,
+    /// Rectangle width
+    pub width: f32,
+    /// Rectangle height
+    pub height: f32,
+}
+
+impl Rectangle {
+    /// Create a rectangle from its top-left corner and size
+    #[inline]
+    pub const fn new(x: f32, y: f32, width: f32, height: f32) -> Self {
+        Self { x, y, width, height }
+    }
+
+    /// Whether `point` lies inside the rectangle
+    #[inline]
+    pub const fn contains(&self, point: Vector2) -> bool {
+        point.x >= self.x && point.x < self.x + self.width
+            && point.y >= self.y && point.y < self.y + self.height
+    }
+
+    #[inline]
+    const fn into_sys(self) -> sys::Rectangle {
+        // SAFETY: `Rectangle` is repr(C) with the same fields as `sys::Rectangle`
+        unsafe { std::mem::transmute(self) }
+    }
+
+    #[inline]
+    const fn from_sys(value: sys::Rectangle) -> Self {
+        // SAFETY: `Rectangle` is repr(C) with the same fields as `sys::Rectangle`
+        unsafe { std::mem::transmute(value) }
+    }
+}
+
+impl From<sys::Rectangle> for Rectangle {
+    #[inline]
+    fn from(value: sys::Rectangle) -> Self {
+        Self::from_sys(value)
+    }
+}
+
+impl From<Rectangle> for sys::Rectangle {
+    #[inline]
+    fn from(value: Rectangle) -> Self {
+        value.into_sys()
+    }
+}
+
 sys_enum_wrapper!{
 /// Keyboard keys (US keyboard layout)
 ///
@@ -606,7 +789,7 @@ pub enum KeyboardKey {
 sys_enum_wrapper! {
 /// Mouse buttons
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
-pub enum MouseButton {
+pub enum MouseButton as "Mouse." {
     /// Mouse button left
     Left := MOUSE_BUTTON_LEFT,
     /// Mouse button right
@@ -656,7 +839,7 @@ pub enum MouseCursor {
 sys_enum_wrapper! {
 /// Gamepad buttons
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
-pub enum GamepadButton {
+pub enum GamepadButton as "Gamepad." {
     _ := GAMEPAD_BUTTON_UNKNOWN,
     /// Gamepad left DPAD up button
     LeftFaceUp := GAMEPAD_BUTTON_LEFT_FACE_UP,
@@ -698,7 +881,7 @@ pub enum GamepadButton {
 sys_enum_wrapper! {
 /// Gamepad axes
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
-pub enum GamepadAxis {
+pub enum GamepadAxis as "GamepadAxis." {
     /// Gamepad left stick X axis
     LeftX := GAMEPAD_AXIS_LEFT_X,
     /// Gamepad left stick Y axis
@@ -714,6 +897,21 @@ pub enum GamepadAxis {
 }
 }
 
+sys_enum_wrapper! {
+/// Touch actions, see [`GestureEvent`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TouchAction as "Touch." {
+    /// A finger was lifted
+    Up := TOUCH_ACTION_UP,
+    /// A finger touched the screen
+    Down := TOUCH_ACTION_DOWN,
+    /// A finger moved
+    Move := TOUCH_ACTION_MOVE,
+    /// The touch was interrupted
+    Cancel := TOUCH_ACTION_CANCEL,
+}
+}
+
 sys_enum_wrapper! {
 /// Pixel formats
 ///
@@ -974,13 +1172,20 @@ impl Image {
     }
 }
 
+/// Marker that makes a type `!Send + !Sync`
+///
+/// Raylib's window, input and drawing state belong to the thread that opened the window.
+type NotSendSync = PhantomData<*const ()>;
+
 /// Handle to the open window
 ///
 /// Cannot call [`Window::draw`] or [`Window::texture_mode`] and does not close the window.
 /// Most window-related methods are implemented on this structure instead of [`Window`].
-pub struct WindowInner(());
+///
+/// GPU resources loaded through it borrow the [`WindowContext`] for `'ctx`.
+pub struct WindowInner<'ctx>(PhantomData<&'ctx mut WindowContext>);
 
-impl WindowInner {
+impl WindowInner<'_> {
     /// Check if application should close ([`sys::KeyboardKey::KEY_ESCAPE`] pressed or windows close icon clicked)
     #[inline]
     pub fn should_close(&self) -> bool {
@@ -1137,6 +1342,200 @@ impl WindowInner {
         }
     }
 
+    /// Set window dimensions
+    #[inline]
+    pub fn set_window_size(&mut self, width: u32, height: u32) {
+        // SAFETY: Existence of `WindowInner` proves window is initialized
+        unsafe {
+            low::set_window_size(width, height);
+        }
+    }
+
+    /// Set monitor for the current window
+    ///
+    /// # Panics
+    /// - `monitor` is not less than [`WindowInner::get_monitor_count()`]
+    #[inline]
+    pub fn set_window_monitor(&mut self, monitor: usize) {
+        assert!(monitor < self.get_monitor_count(), "monitor {monitor} should be connected");
+        // SAFETY: Existence of `WindowInner` proves window is initialized
+        unsafe {
+            low::set_window_monitor(monitor);
+        }
+    }
+
+    /// Set window minimum dimensions (for [`ConfigFlags::WINDOW_RESIZABLE`](low::types::ConfigFlags::WINDOW_RESIZABLE))
+    #[inline]
+    pub fn set_window_min_size(&mut self, width: u32, height: u32) {
+        // SAFETY: Existence of `WindowInner` proves window is initialized
+        unsafe {
+            low::set_window_min_size(width, height);
+        }
+    }
+
+    /// Set window maximum dimensions (for [`ConfigFlags::WINDOW_RESIZABLE`](low::types::ConfigFlags::WINDOW_RESIZABLE))
+    #[inline]
+    pub fn set_window_max_size(&mut self, width: u32, height: u32) {
+        // SAFETY: Existence of `WindowInner` proves window is initialized
+        unsafe {
+            low::set_window_max_size(width, height);
+        }
+    }
+
+    /// Set window opacity [0.0..=1.0]
+    ///
+    /// # Panics
+    /// - `opacity` is outside of `0.0..=1.0`
+    #[inline]
+    pub fn set_window_opacity(&mut self, opacity: f32) {
+        // SAFETY: Existence of `WindowInner` proves window is initialized
+        unsafe {
+            low::set_window_opacity(opacity);
+        }
+    }
+
+    /// Get number of connected monitors
+    #[inline]
+    pub fn get_monitor_count(&self) -> usize {
+        // SAFETY: Existence of `WindowInner` proves window is initialized
+        unsafe { low::get_monitor_count() }
+    }
+
+    /// Get current monitor where window is placed, [`None`] if Raylib can't tell
+    #[inline]
+    pub fn get_current_monitor(&self) -> Option<usize> {
+        // SAFETY: Existence of `WindowInner` proves window is initialized
+        unsafe { low::get_current_monitor() }.ok()
+    }
+
+    /// Get window position XY on monitor
+    #[inline]
+    pub fn get_window_position(&self) -> Vector2 {
+        // SAFETY: Existence of `WindowInner` proves window is initialized
+        unsafe { Vector2::from_sys(low::get_window_position()) }
+    }
+
+    /// Get window scale DPI factor
+    #[inline]
+    pub fn get_window_scale_dpi(&self) -> Vector2 {
+        // SAFETY: Existence of `WindowInner` proves window is initialized
+        unsafe { Vector2::from_sys(low::get_window_scale_dpi()) }
+    }
+
+    /// Get current screen width
+    #[inline]
+    pub fn get_screen_width(&self) -> u32 {
+        // SAFETY: Existence of `WindowInner` proves window is initialized
+        unsafe { low::get_screen_width() }
+    }
+
+    /// Get current screen height
+    #[inline]
+    pub fn get_screen_height(&self) -> u32 {
+        // SAFETY: Existence of `WindowInner` proves window is initialized
+        unsafe { low::get_screen_height() }
+    }
+
+    /// Set target FPS (maximum)
+    #[inline]
+    pub fn set_target_fps(&mut self, fps: u32) {
+        // SAFETY: Existence of `WindowInner` proves window is initialized
+        unsafe {
+            low::set_target_fps(fps);
+        }
+        #[cfg(feature = "custom_frame_control")]
+        frame_control::set_target_fps(fps);
+    }
+
+    /// Get duration for last frame drawn (delta time)
+    #[cfg(not(feature = "custom_frame_control"))]
+    #[inline]
+    pub fn get_frame_duration(&self) -> Duration {
+        // SAFETY: Existence of `WindowInner` proves window is initialized
+        unsafe { low::get_frame_duration() }
+    }
+
+    /// Get duration for last frame shown by [`Window::draw()`] or [`Window::present()`] (delta time)
+    #[cfg(feature = "custom_frame_control")]
+    #[inline]
+    pub fn get_frame_duration(&self) -> Duration {
+        frame_control::frame_duration()
+    }
+
+    /// Get elapsed duration since the window was opened
+    #[inline]
+    pub fn get_elapsed(&self) -> Duration {
+        // SAFETY: Existence of `WindowInner` proves window is initialized
+        unsafe { low::get_elapsed() }
+    }
+
+    /// Get current FPS
+    #[cfg(not(feature = "custom_frame_control"))]
+    #[inline]
+    pub fn get_fps(&self) -> u32 {
+        // SAFETY: Existence of `WindowInner` proves window is initialized
+        unsafe { low::get_fps() }
+    }
+
+    /// Get current FPS, averaged over the last frames shown by [`Window::draw()`] or [`Window::present()`]
+    #[cfg(feature = "custom_frame_control")]
+    #[inline]
+    pub fn get_fps(&self) -> u32 {
+        frame_control::fps()
+    }
+
+    /// Set clipboard text content
+    ///
+    /// # Panics
+    /// - [`IntoCStr::into_cstr()`] fails for `text`
+    #[inline]
+    pub fn set_clipboard_text(&mut self, text: impl IntoCStr) {
+        let text = text.into_cstr().unwrap();
+        // SAFETY: Existence of `WindowInner` proves window is initialized
+        unsafe {
+            low::set_clipboard_text(text.as_ref());
+        }
+    }
+
+    /// Get clipboard text content, [`None`] if the clipboard holds no text
+    ///
+    /// Invalid UTF-8 is replaced with `U+FFFD`.
+    #[inline]
+    pub fn get_clipboard_text(&self) -> Option<String> {
+        // SAFETY: Existence of `WindowInner` proves window is initialized
+        unsafe {
+            low::get_clipboard_text()
+                .map(|text| text.to_string_lossy().into_owned())
+        }
+    }
+
+    /// Enable waiting for events when the frame ends, no automatic event polling
+    ///
+    /// See [`Window::run_event_loop()`] for switching between waiting and polling automatically.
+    #[inline]
+    pub fn enable_event_waiting(&mut self) {
+        // SAFETY: Existence of `WindowInner` proves window is initialized
+        unsafe {
+            low::enable_event_waiting();
+        }
+    }
+
+    /// Disable waiting for events when the frame ends, automatic event polling
+    #[inline]
+    pub fn disable_event_waiting(&mut self) {
+        // SAFETY: Existence of `WindowInner` proves window is initialized
+        unsafe {
+            low::disable_event_waiting();
+        }
+    }
+
+    /// Check if a file has been dropped into window
+    #[inline]
+    pub fn is_file_dropped(&self) -> bool {
+        // SAFETY: Existence of `WindowInner` proves window is initialized
+        unsafe { low::is_file_dropped() }
+    }
+
     /// Measure string width for default font
     ///
     /// # Panics
@@ -1148,14 +1547,16 @@ impl WindowInner {
         unsafe { low::measure_text(text.as_ref(), font_size) }
     }
 
-    /// Measure string size for Font
+    /// Measure string size for Font, or for the default font if `font` is [`None`]
     ///
     /// # Panics
     /// - [`IntoCStr::into_cstr()`] fails for `text`
     #[inline]
-    pub fn measure_text_ex(&self, font: sys::Font, text: impl IntoCStr, font_size: f32, spacing: f32) -> Vector2 {
+    pub fn measure_text_ex(&self, font: Option<&Font<'_>>, text: impl IntoCStr, font_size: f32, spacing: f32) -> Vector2 {
         let text = text.into_cstr().unwrap();
         // SAFETY: Existence of `WindowInner` proves Raylib is initialized
+        let font = font.map_or_else(|| unsafe { low::get_font_default() }, Font::as_sys);
+        // SAFETY: Existence of `WindowInner` proves Raylib is initialized
         unsafe { Vector2::from_sys(low::measure_text_ex(font, text.as_ref(), font_size, spacing)) }
     }
 
@@ -1308,6 +1709,8 @@ impl WindowInner {
 
     /// Set internal gamepad mappings (SDL_GameControllerDB)
     ///
+    /// See [`WindowInner::load_gamepad_mappings()`] to load a whole database file.
+    ///
     /// # Panics
     /// - [`IntoCStr::into_cstr()`] fails for any string in `mappings`
     #[inline]
@@ -1476,90 +1879,268 @@ impl WindowInner {
         // SAFETY: Existence of `WindowInner` proves Raylib is initialized
         unsafe { low::get_touch_point_count() }
     }
+
+    // Gestures and Touch Handling Functions (Module: rgestures)
+
+    /// Enable only desired gestures to be detected
+    #[inline]
+    pub fn set_gestures_enabled(&mut self, flags: Gesture) {
+        // SAFETY: Existence of `WindowInner` proves Raylib is initialized
+        unsafe {
+            low::set_gestures_enabled(sys::Gesture(flags.bits() as _));
+        }
+    }
+
+    /// Check if a gesture have been detected
+    #[inline]
+    pub fn is_gesture_detected(&self, gesture: Gesture) -> bool {
+        // SAFETY: Existence of `WindowInner` proves Raylib is initialized
+        unsafe { low::is_gesture_detected(sys::Gesture(gesture.bits() as _)) }
+    }
+
+    /// Get latest detected gesture
+    #[inline]
+    pub fn get_gesture_detected(&self) -> Gesture {
+        // SAFETY: Existence of `WindowInner` proves Raylib is initialized
+        Gesture::from_bits_truncate(unsafe { low::get_gesture_detected() }.0 as _)
+    }
+
+    /// Get gesture hold time
+    ///
+    /// NOTE: time is calculated on current gesture HOLD
+    #[inline]
+    pub fn get_gesture_hold_duration(&self) -> Duration {
+        // SAFETY: Existence of `WindowInner` proves Raylib is initialized
+        Duration::try_from_secs_f32(unsafe { low::get_gesture_hold_duration() }).unwrap_or_default()
+    }
+
+    /// Get gesture drag vector (between initial touch point to current)
+    #[inline]
+    pub fn get_gesture_drag_vector(&self) -> Vector2 {
+        // SAFETY: Existence of `WindowInner` proves Raylib is initialized
+        unsafe { low::get_gesture_drag_vector().into() }
+    }
+
+    /// Get gesture drag angle in degrees, horizontal-right is 0, counterclockwise
+    #[inline]
+    pub fn get_gesture_drag_angle(&self) -> f32 {
+        // SAFETY: Existence of `WindowInner` proves Raylib is initialized
+        unsafe { low::get_gesture_drag_angle() }
+    }
+
+    /// Get gesture pinch delta; distance between two pinch points
+    #[inline]
+    pub fn get_gesture_pinch_vector(&self) -> Vector2 {
+        // SAFETY: Existence of `WindowInner` proves Raylib is initialized
+        unsafe { low::get_gesture_pinch_vector().into() }
+    }
+
+    /// Get gesture pinch angle in degrees, horizontal-right is 0, counterclockwise
+    #[inline]
+    pub fn get_gesture_pinch_angle(&self) -> f32 {
+        // SAFETY: Existence of `WindowInner` proves Raylib is initialized
+        unsafe { low::get_gesture_pinch_angle() }
+    }
+
+    /// Process gesture event and translate it into gestures
+    ///
+    /// Raylib feeds touch and mouse input through this already;
+    /// call it to simulate touches, such as to test touch UIs on desktop.
+    #[inline]
+    pub fn process_gesture_event(&mut self, event: &GestureEvent) {
+        // SAFETY: Existence of `WindowInner` proves Raylib is initialized
+        unsafe {
+            low::process_gesture_event(event.as_sys());
+        }
+    }
 }
 
-/// Handle for Raylib window functions
+/// Handle for Raylib window functions, see [`WindowContext::window()`]
 ///
-/// Closes the window upon exiting scope
-pub struct Window(WindowInner);
+/// Only one can exist at a time, which keeps draw handles from being nested.
+pub struct Window<'ctx>(WindowInner<'ctx>);
 
-impl std::ops::Deref for Window {
-    type Target = WindowInner;
+impl<'ctx> std::ops::Deref for Window<'ctx> {
+    type Target = WindowInner<'ctx>;
 
     #[inline]
-    fn deref(&self) -> &WindowInner {
+    fn deref(&self) -> &WindowInner<'ctx> {
         &self.0
     }
 }
 
-impl std::ops::DerefMut for Window {
+impl<'ctx> std::ops::DerefMut for Window<'ctx> {
     #[inline]
-    fn deref_mut(&mut self) -> &mut WindowInner {
+    fn deref_mut(&mut self) -> &mut WindowInner<'ctx> {
         &mut self.0
     }
 }
 
-impl Drop for Window {
+/// Owner of the window and OpenGL context
+///
+/// Closes the window upon exiting scope. Every GPU resource borrows the context it was loaded into,
+/// so textures, render textures, shaders and fonts must be dropped before it.
+pub struct WindowContext(NotSendSync);
+
+impl Drop for WindowContext {
     /// Close window and unload OpenGL context
     #[inline]
     fn drop(&mut self) {
-        // SAFETY: Existence of `Window` proves window is initialized and not drawing
+        // SAFETY:
+        // - Existence of `WindowContext` proves window is initialized
+        // - Dropping requires `WindowContext` to be unborrowed, so no `Window` (and thus no draw handle)
+        //   or GPU resource exists anymore
         unsafe {
             low::close_window();
         }
+        WindowContext::release();
     }
 }
 
-impl Window {
+/// Thread that owns the open window, [`None`] while no window is open
+///
+/// Claimed before Raylib is initialized and released after the window is closed,
+/// so only one thread can be opening, using or closing a window at a time.
+static WINDOW_OWNER: Mutex<Option<ThreadId>> = Mutex::new(None);
+
+/// An error from opening a [`WindowContext`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WindowInitError {
+    /// A window is already open on the calling thread
+    AlreadyOpen,
+    /// A window is open on another thread
+    OpenOnOtherThread(ThreadId),
+    /// Raylib failed to create the window or its OpenGL context
+    InitFailed,
+}
+
+impl std::fmt::Display for WindowInitError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::AlreadyOpen => f.write_str("a window is already open on this thread"),
+            Self::OpenOnOtherThread(id) => write!(f, "a window is already open on thread {id:?}"),
+            Self::InitFailed => f.write_str("failed to initialize window or OpenGL context"),
+        }
+    }
+}
+
+impl std::error::Error for WindowInitError {}
+
+impl WindowContext {
+    /// Claim the window for the calling thread
+    fn claim() -> Result<(), WindowInitError> {
+        let mut owner = WINDOW_OWNER.lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        let current = std::thread::current().id();
+        match *owner {
+            None => {
+                *owner = Some(current);
+                Ok(())
+            }
+            Some(id) if id == current => Err(WindowInitError::AlreadyOpen),
+            Some(id) => Err(WindowInitError::OpenOnOtherThread(id)),
+        }
+    }
+
+    /// Give up the claim from [`WindowContext::claim()`]
+    fn release() {
+        *WINDOW_OWNER.lock()
+            .unwrap_or_else(PoisonError::into_inner) = None;
+    }
+
     /// Initialize window and OpenGL context
     ///
+    /// Only one window can be open at a time. The thread that opens it owns it;
+    /// [`WindowContext`], [`Window`] and [`WindowInner`] cannot be sent to or shared with other threads.
+    ///
     /// # Panics
     /// - [`IntoCStr::into_cstr()`] fails for `title`
     #[inline]
-    pub fn init(width: u32, height: u32, title: impl IntoCStr) -> Option<Self> {
-        // SAFETY:
-        // - `is_window_ready` returns a static bool that is initialized with false
-        // - TODO: Is this thread-safe??
-        if !unsafe { low::is_window_ready() } {
-            let title = title.into_cstr().unwrap();
-            // SAFETY:
-            // - Window must be uninitialized to reach this branch
-            unsafe {
-                low::init_window(width, height, title.as_ref());
-            }
-            // SAFETY:
-            // - `is_window_ready` returns a static bool that is initialized with false
-            // - TODO: Is this thread-safe??
-            if unsafe { low::is_window_ready() } {
-                return Some(Self(WindowInner(())));
-            }
+    pub fn init(width: u32, height: u32, title: impl IntoCStr) -> Result<Self, WindowInitError> {
+        let title = title.into_cstr().unwrap();
+        Self::claim()?;
+        // SAFETY: The claim was just taken
+        unsafe { Self::init_claimed(width, height, title.as_ref()) }
+    }
+
+    /// Initialize window and OpenGL context, releasing the claim on failure
+    ///
+    /// # Safety
+    ///
+    /// The calling thread must have just taken the claim with [`WindowContext::claim()`].
+    unsafe fn init_claimed(width: u32, height: u32, title: &std::ffi::CStr) -> Result<Self, WindowInitError> {
+        // SAFETY: The claim guarantees no other `WindowContext` exists or is being opened
+        if unsafe { low::is_window_ready() } {
+            // Opened through `low` without a `WindowContext`
+            Self::release();
+            return Err(WindowInitError::AlreadyOpen);
+        }
+        // SAFETY: Window is uninitialized and the claim keeps other threads from initializing it
+        unsafe {
+            low::init_window(width, height, title);
+        }
+        // SAFETY: The claim guarantees no other thread is changing the window state
+        if unsafe { low::is_window_ready() } {
+            #[cfg(feature = "custom_frame_control")]
+            frame_control::reset_frame_clock();
+            Ok(Self(PhantomData))
+        } else {
+            Self::release();
+            Err(WindowInitError::InitFailed)
         }
-        None
     }
 
+    /// Get the handle for window functions, drawing and loading GPU resources
+    ///
+    /// Resources loaded through the handle borrow the context for as long as the handle does,
+    /// so call this once and keep the [`Window`] for the rest of the program.
+    #[inline]
+    pub fn window(&mut self) -> Window<'_> {
+        Window(WindowInner(PhantomData))
+    }
+}
+
+impl<'ctx> Window<'ctx> {
     /// Setup canvas (framebuffer) to start drawing
+    ///
+    /// With the `custom_frame_control` feature this still shows the frame, waits for the target FPS and polls input
+    /// afterwards, see `Window::render()` for drawing without doing so.
     #[inline]
-    pub fn draw<'w>(&'w mut self, f: impl for<'d> FnOnce(&'w mut WindowInner, &'d mut Drawing, &'d mut BaseDrawMode)) {
+    pub fn draw<'w>(&'w mut self, f: impl for<'d> FnOnce(&'w mut WindowInner<'ctx>, &'d mut Drawing, &'d mut BaseDrawMode)) {
         // SAFETY:
         // - Existence of `Window` proves window is initialized
         // - `Window` is borrowed exclusively, and `WindowInner` (which cannot create draw handles) is passed to `f` instead
         unsafe {
             low::begin_drawing();
         }
-        f(&mut self.0, &mut Drawing(()), &mut BaseDrawMode(()));
+        f(&mut self.0, &mut Drawing(PhantomData), &mut BaseDrawMode(PhantomData));
+        // With `custom_frame_control`, ending the frame neither swaps buffers, waits for the target FPS nor polls input
+        #[cfg(feature = "custom_frame_control")]
+        {
+            // SAFETY: The `Drawing` passed to `f` has been dropped, ending the frame
+            unsafe {
+                low::swap_screen_buffer();
+            }
+            // SAFETY: Existence of `Window` proves window is initialized
+            frame_control::end_frame(|remaining| unsafe { low::wait_duration(remaining) });
+            // SAFETY: The frame has ended, so this is not called while drawing
+            unsafe {
+                low::poll_input_events();
+            }
+        }
     }
 
     /// Begin drawing to render texture
     #[inline]
-    pub fn texture_mode<'w>(&'w mut self, target: &mut sys::RenderTexture2D, f: impl for<'d> FnOnce(&'w mut WindowInner, &'d mut TextureMode, &'d mut BaseDrawMode)) {
+    pub fn texture_mode<'w>(&'w mut self, target: &mut RenderTexture<'_>, f: impl for<'d> FnOnce(&'w mut WindowInner<'ctx>, &'d mut TextureMode, &'d mut BaseDrawMode)) {
         // SAFETY:
         // - Existence of `Window` proves window is initialized
         // - `Window` is borrowed exclusively, and `WindowInner` (which cannot create draw handles) is passed to `f` instead
-        // - `target` is borrowed exclusively and not passed to `f`
+        // - `target` is borrowed exclusively and not passed to `f`, so its texture cannot be drawn into itself
         unsafe {
-            low::begin_texture_mode(*target);
+            low::begin_texture_mode(target.as_sys());
         }
-        f(&mut self.0, &mut TextureMode(()), &mut BaseDrawMode(()))
+        f(&mut self.0, &mut TextureMode(PhantomData), &mut BaseDrawMode(PhantomData))
     }
 }
 
@@ -1809,7 +2390,7 @@ impl CSSPalette for Color {}
 /// A handle for draw functions available in [`Window::draw`]
 ///
 /// Ends drawing when dropped
-pub struct Drawing(());
+pub struct Drawing(NotSendSync);
 
 impl Drop for Drawing {
     /// End canvas drawing and swap buffers (double buffering)
@@ -1827,7 +2408,7 @@ impl Drop for Drawing {
 /// A handle for draw functions available in [`Window::texture_mode`]
 ///
 /// Ends texture mode when dropped
-pub struct TextureMode(());
+pub struct TextureMode(NotSendSync);
 
 impl Drop for TextureMode {
     /// Ends drawing to render texture
@@ -1854,6 +2435,7 @@ mod private {
     impl<T: ?Sized + DrawMode> SealedDrawMode for &mut T {}
     impl<T: ?Sized + DrawMode> SealedDrawMode for DrawModeEnum<'_, T> {}
     impl<T: ?Sized + DrawMode> SealedDrawMode for ScissorMode<'_, T> {}
+    impl<T: ?Sized + DrawMode> SealedDrawMode for ShaderMode<'_, T> {}
 }
 
 /// Raylib drawing functions
@@ -1885,6 +2467,67 @@ pub trait Draw: private::SealedDraw {
             low::draw_rectangle(pos_x, pos_y, width, height, color.into());
         }
     }
+
+    /// Draw text using font and additional parameters
+    ///
+    /// # Panics
+    /// - [`IntoCStr::into_cstr()`] fails for `text`
+    #[inline]
+    fn draw_text_ex(&mut self, font: &Font<'_>, text: impl IntoCStr, position: Vector2, font_size: f32, spacing: f32, tint: Color) {
+        let text = text.into_cstr().unwrap();
+        // SAFETY:
+        // - `Draw` has sealed implementation restricted to draw handles
+        // - `font` borrows the `WindowContext` it was loaded into, proving it has not been unloaded
+        unsafe {
+            low::draw_text_ex(font.as_sys(), text.as_ref(), position.into(), font_size, spacing, tint.into());
+        }
+    }
+
+    /// Draw a texture
+    #[inline]
+    fn draw_texture(&mut self, texture: &Texture<'_>, pos_x: i32, pos_y: i32, tint: Color) {
+        // SAFETY:
+        // - `Draw` has sealed implementation restricted to draw handles
+        // - `texture` borrows the `WindowContext` it was loaded into, proving it has not been unloaded
+        unsafe {
+            low::draw_texture(texture.as_sys(), pos_x, pos_y, tint.into());
+        }
+    }
+
+    /// Draw a texture with position defined as [`Vector2`]
+    #[inline]
+    fn draw_texture_v(&mut self, texture: &Texture<'_>, position: Vector2, tint: Color) {
+        // SAFETY:
+        // - `Draw` has sealed implementation restricted to draw handles
+        // - `texture` borrows the `WindowContext` it was loaded into, proving it has not been unloaded
+        unsafe {
+            low::draw_texture_v(texture.as_sys(), position.into(), tint.into());
+        }
+    }
+
+    /// Draw a texture with extended parameters
+    #[inline]
+    fn draw_texture_ex(&mut self, texture: &Texture<'_>, position: Vector2, rotation: f32, scale: f32, tint: Color) {
+        // SAFETY:
+        // - `Draw` has sealed implementation restricted to draw handles
+        // - `texture` borrows the `WindowContext` it was loaded into, proving it has not been unloaded
+        unsafe {
+            low::draw_texture_ex(texture.as_sys(), position.into(), rotation, scale, tint.into());
+        }
+    }
+
+    /// Draw the `source` part of a texture into `dest`, rotated by `rotation` degrees around `origin`
+    ///
+    /// `origin` is relative to `dest`. A negative `source` width or height flips the texture.
+    #[inline]
+    fn draw_texture_pro(&mut self, texture: &Texture<'_>, source: Rectangle, dest: Rectangle, origin: Vector2, rotation: f32, tint: Color) {
+        // SAFETY:
+        // - `Draw` has sealed implementation restricted to draw handles
+        // - `texture` borrows the `WindowContext` it was loaded into, proving it has not been unloaded
+        unsafe {
+            low::draw_texture_pro(texture.as_sys(), source.into(), dest.into(), origin.into(), rotation, tint.into());
+        }
+    }
 }
 
 impl Draw for Drawing {}
@@ -1901,6 +2544,8 @@ pub enum DrawModeEnum<'a, M: ?Sized + DrawMode + 'a> {
 
     /// See [`ScissorMode`]
     Scissor(ScissorMode<'a, M>),
+    /// See [`ShaderMode`]
+    Shader(ShaderMode<'a, M>),
 }
 
 /// Conversion to [`DrawModeEnum`], shared by both [`DrawMode`] implementors and their references
@@ -1955,7 +2600,7 @@ pub trait DrawMode: private::SealedDrawMode {}
 impl<'a, M: ?Sized + DrawMode + 'a> DrawMode for DrawModeEnum<'a, M> {}
 
 /// The default draw mode -- created alongside [`Drawing`] or [`TextureMode`]
-pub struct BaseDrawMode(());
+pub struct BaseDrawMode(NotSendSync);
 
 impl DrawMode for BaseDrawMode {}
 
@@ -1998,3 +2643,46 @@ impl<'a, M: ?Sized + DrawMode> IntoDrawModeEnum<'a, M> for ScissorMode<'a, M> {
 }
 
 impl<'a, M: ?Sized + DrawMode> DrawMode for ScissorMode<'a, M> {}
+
+/// Raylib shader mode, drawing with a custom [`Shader`]
+pub struct ShaderMode<'a, M: ?Sized + DrawMode>(PhantomData<&'a mut M>);
+
+impl<M: ?Sized + DrawMode> Drop for ShaderMode<'_, M> {
+    /// End custom shader drawing (use default shader)
+    #[inline]
+    fn drop(&mut self) {
+        // SAFETY:
+        // - `begin_shader_mode` must have been called to construct `ShaderMode`
+        // - `ShaderMode` contains an inaccessible, exclusive reference to the
+        //   outer draw mode, requiring it to outlive this one
+        unsafe {
+            low::end_shader_mode();
+        }
+    }
+}
+
+impl<'a, M: ?Sized + DrawMode> ShaderMode<'a, M> {
+    /// Begin custom shader drawing
+    ///
+    /// `shader` stays borrowed until the mode ends, so it cannot be unloaded while in use.
+    #[inline]
+    pub fn begin(_outer_mode: &'a mut M, shader: &'a Shader<'_>) -> ShaderMode<'a, M> {
+        // SAFETY:
+        // - `_outer_mode` must implement `DrawMode`
+        //   - `DrawMode` has sealed implementation restricted to types originating from a draw handle
+        // - `shader` borrows the `WindowContext` it was loaded into, proving it has not been unloaded
+        unsafe {
+            low::begin_shader_mode(shader.as_sys());
+        }
+        Self(PhantomData)
+    }
+}
+
+impl<'a, M: ?Sized + DrawMode> IntoDrawModeEnum<'a, M> for ShaderMode<'a, M> {
+    #[inline]
+    fn into_enum(self) -> DrawModeEnum<'a, M> {
+        DrawModeEnum::Shader(self)
+    }
+}
+
+impl<'a, M: ?Sized + DrawMode> DrawMode for ShaderMode<'a, M> {}
@@ -0,0 +1,165 @@
+//! Connected monitors and placing the window on them
+
+use super::*;
+
+/// Snapshot of a connected monitor, see [`WindowInner::monitors()`]
+///
+/// Positions are screen coordinates on the virtual desktop spanning every monitor.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Monitor {
+    /// Index used by Raylib's monitor functions
+    pub index: usize,
+    /// Human-readable name, empty if the platform does not report one
+    pub name: String,
+    /// Left edge on the virtual desktop
+    pub x: i32,
+    /// Top edge on the virtual desktop
+    pub y: i32,
+    /// Width of the current video mode
+    pub width: u32,
+    /// Height of the current video mode
+    pub height: u32,
+    /// Physical width in millimetres
+    pub physical_width: u32,
+    /// Physical height in millimetres
+    pub physical_height: u32,
+    /// Refresh rate in Hz
+    pub refresh_rate: u32,
+}
+
+impl Monitor {
+    /// # Safety
+    ///
+    /// The window must be initialized.
+    unsafe fn query(index: usize) -> Self {
+        // SAFETY: Caller guarantees the window is initialized
+        unsafe {
+            let position = low::get_monitor_position(index);
+            Self {
+                index,
+                name: low::get_monitor_name(index)
+                    .map(|name| name.to_string_lossy().into_owned())
+                    .unwrap_or_default(),
+                x: position.x as i32,
+                y: position.y as i32,
+                width: low::get_monitor_width(index),
+                height: low::get_monitor_height(index),
+                physical_width: low::get_monitor_physical_width(index),
+                physical_height: low::get_monitor_physical_height(index),
+                refresh_rate: low::get_monitor_refresh_rate(index),
+            }
+        }
+    }
+
+    /// Whether the point `(x, y)` lies on this monitor
+    #[inline]
+    pub const fn contains(&self, x: i32, y: i32) -> bool {
+        x >= self.x && (x as i64) < self.x as i64 + self.width as i64
+            && y >= self.y && (y as i64) < self.y as i64 + self.height as i64
+    }
+
+    /// Position that centers a `width` by `height` window on this monitor
+    #[inline]
+    pub const fn centered(&self, width: u32, height: u32) -> (i32, i32) {
+        (
+            self.x + ((self.width as i64 - width as i64) / 2) as i32,
+            self.y + ((self.height as i64 - height as i64) / 2) as i32,
+        )
+    }
+
+    /// Move the position `(x, y)` of a `width` by `height` window so that as much of it as possible is on this monitor
+    ///
+    /// A window larger than the monitor is aligned to the monitor's top-left corner.
+    #[inline]
+    pub fn clamped(&self, x: i32, y: i32, width: u32, height: u32) -> (i32, i32) {
+        let max_x = self.x + self.width.saturating_sub(width) as i32;
+        let max_y = self.y + self.height.saturating_sub(height) as i32;
+        (x.clamp(self.x, max_x), y.clamp(self.y, max_y))
+    }
+
+    /// Area available to a window, as `(x, y, width, height)`
+    ///
+    /// The monitor's size is divided by `dpi_scale` ([`WindowInner::get_window_scale_dpi()`]), because window
+    /// sizes are given in screen coordinates. Raylib does not report areas reserved by the desktop,
+    /// such as task bars and docks, so `margin` is kept free on every side instead.
+    pub fn work_area(&self, dpi_scale: Vector2, margin: u32) -> (i32, i32, u32, u32) {
+        let scale = |size: u32, dpi: f32| {
+            let size = if dpi > 0.0 { size as f32 / dpi } else { size as f32 };
+            (size as u32).saturating_sub(margin.saturating_mul(2)).max(1)
+        };
+        (
+            self.x + margin as i32,
+            self.y + margin as i32,
+            scale(self.width, dpi_scale.x),
+            scale(self.height, dpi_scale.y),
+        )
+    }
+}
+
+impl WindowInner<'_> {
+    /// Get a snapshot of a connected monitor, [`None`] if `index` is not less than [`WindowInner::get_monitor_count()`]
+    #[inline]
+    pub fn monitor(&self, index: usize) -> Option<Monitor> {
+        // SAFETY: Existence of `WindowInner` proves window is initialized
+        (index < self.get_monitor_count()).then(|| unsafe { Monitor::query(index) })
+    }
+
+    /// Iterate over snapshots of every connected monitor
+    #[inline]
+    pub fn monitors(&self) -> impl ExactSizeIterator<Item = Monitor> {
+        // SAFETY: Existence of `WindowInner` proves window is initialized, and the iterator borrows it
+        (0..self.get_monitor_count()).map(|index| unsafe { Monitor::query(index) })
+    }
+
+    /// Get a snapshot of the monitor the window is placed on
+    #[inline]
+    pub fn current_monitor(&self) -> Option<Monitor> {
+        self.get_current_monitor().and_then(|index| self.monitor(index))
+    }
+
+    /// Center the window on `monitor`
+    #[inline]
+    pub fn center_window(&mut self, monitor: &Monitor) {
+        let (x, y) = monitor.centered(self.get_screen_width(), self.get_screen_height());
+        self.set_window_position(x, y);
+    }
+
+    /// Move the window to `monitor`, keeping its offset from the top-left corner of its current monitor
+    ///
+    /// A fullscreen window switches monitor instead, keeping its video mode.
+    /// The window is kept within the bounds of `monitor` where it fits.
+    pub fn move_window_to_monitor(&mut self, monitor: &Monitor) {
+        if self.is_window_fullscreen() {
+            if monitor.index < self.get_monitor_count() {
+                self.set_window_monitor(monitor.index);
+            }
+            return;
+        }
+        let position = self.get_window_position();
+        let (x, y) = (position.x as i32, position.y as i32);
+        let (offset_x, offset_y) = self.current_monitor()
+            .map_or((0, 0), |current| (x - current.x, y - current.y));
+        let (x, y) = monitor.clamped(
+            monitor.x + offset_x,
+            monitor.y + offset_y,
+            self.get_screen_width(),
+            self.get_screen_height(),
+        );
+        self.set_window_position(x, y);
+    }
+
+    /// Resize and move the window to fill the [work area](Monitor::work_area) of `monitor`
+    ///
+    /// A maximized or fullscreen window is restored to windowed mode first.
+    pub fn fit_window_to_monitor(&mut self, monitor: &Monitor, margin: u32) {
+        if self.is_window_fullscreen() {
+            self.toggle_fullscreen();
+        }
+        if self.is_window_maximized() {
+            self.restore_window();
+        }
+        let (x, y, width, height) = monitor.work_area(self.get_window_scale_dpi(), margin);
+        self.set_window_size(width, height);
+        self.set_window_position(x, y);
+    }
+}
@@ -0,0 +1,340 @@
+//! GPU resources owned by a [`WindowContext`]
+//!
+//! Textures, render textures, shaders and fonts live in the OpenGL context, which is destroyed by
+//! `close_window`. Each resource borrows the [`WindowContext`] it was loaded into, so holding one
+//! past the point the context is dropped is a compile-time error.
+
+use super::*;
+
+/// Texture, tex data stored in GPU memory (VRAM)
+pub struct Texture<'ctx> {
+    texture: sys::Texture2D,
+    _ctx: PhantomData<&'ctx WindowContext>,
+}
+
+impl Drop for Texture<'_> {
+    /// Unload texture from GPU memory (VRAM)
+    #[inline]
+    fn drop(&mut self) {
+        // SAFETY:
+        // - `Texture` is only constructed from a valid texture
+        // - The borrow of `WindowContext` proves the OpenGL context is still alive
+        unsafe {
+            low::unload_texture(self.texture);
+        }
+    }
+}
+
+impl Texture<'_> {
+    /// # Safety
+    ///
+    /// `texture` must be valid, loaded into the context borrowed by `'ctx`, and not unloaded elsewhere.
+    #[inline]
+    unsafe fn from_sys<'ctx>(texture: sys::Texture2D) -> Option<Texture<'ctx>> {
+        // SAFETY: Caller guarantees the context is alive
+        if unsafe { low::is_texture_valid(texture) } {
+            Some(Texture { texture, _ctx: PhantomData })
+        } else {
+            None
+        }
+    }
+
+    #[inline]
+    pub(crate) const fn as_sys(&self) -> sys::Texture2D {
+        self.texture
+    }
+
+    /// Texture base width
+    #[inline]
+    pub const fn width(&self) -> u32 {
+        self.texture.width as u32
+    }
+
+    /// Texture base height
+    #[inline]
+    pub const fn height(&self) -> u32 {
+        self.texture.height as u32
+    }
+
+    /// Mipmap levels, 1 by default
+    #[inline]
+    pub const fn mipmaps(&self) -> u32 {
+        self.texture.mipmaps as u32
+    }
+
+    /// Set texture scaling filter mode
+    #[inline]
+    pub fn set_filter(&mut self, filter: TextureFilter) {
+        // SAFETY: The borrow of `WindowContext` proves the OpenGL context is still alive
+        unsafe {
+            low::set_texture_filter(self.texture, filter.into_sys());
+        }
+    }
+}
+
+/// Framebuffer with color and depth attachments, the target of [`Window::texture_mode()`]
+pub struct RenderTexture<'ctx> {
+    target: sys::RenderTexture2D,
+    // Unloaded together with `target`, never on its own
+    texture: std::mem::ManuallyDrop<Texture<'ctx>>,
+}
+
+impl Drop for RenderTexture<'_> {
+    /// Unload render texture from GPU memory (VRAM)
+    #[inline]
+    fn drop(&mut self) {
+        // SAFETY:
+        // - `RenderTexture` is only constructed from a valid render texture
+        // - The borrow of `WindowContext` proves the OpenGL context is still alive
+        unsafe {
+            low::unload_render_texture(self.target);
+        }
+    }
+}
+
+impl<'ctx> RenderTexture<'ctx> {
+    #[inline]
+    pub(crate) const fn as_sys(&self) -> sys::RenderTexture2D {
+        self.target
+    }
+
+    /// Color buffer attachment, for drawing the render texture
+    #[inline]
+    pub fn texture(&self) -> &Texture<'ctx> {
+        &self.texture
+    }
+
+    /// Set scaling filter mode of the color buffer attachment
+    #[inline]
+    pub fn set_filter(&mut self, filter: TextureFilter) {
+        self.texture.set_filter(filter);
+    }
+
+    /// Render texture width
+    #[inline]
+    pub const fn width(&self) -> u32 {
+        self.target.texture.width as u32
+    }
+
+    /// Render texture height
+    #[inline]
+    pub const fn height(&self) -> u32 {
+        self.target.texture.height as u32
+    }
+}
+
+/// Shader program compiled on the GPU, used through [`ShaderMode`]
+pub struct Shader<'ctx> {
+    shader: sys::Shader,
+    _ctx: PhantomData<&'ctx WindowContext>,
+}
+
+impl Drop for Shader<'_> {
+    /// Unload shader from GPU memory (VRAM)
+    #[inline]
+    fn drop(&mut self) {
+        // SAFETY:
+        // - `Shader` is only constructed from a valid shader
+        // - The borrow of `WindowContext` proves the OpenGL context is still alive
+        unsafe {
+            low::unload_shader(self.shader);
+        }
+    }
+}
+
+impl Shader<'_> {
+    /// # Safety
+    ///
+    /// `shader` must be loaded into the context borrowed by `'ctx` and not unloaded elsewhere.
+    #[inline]
+    unsafe fn from_sys<'ctx>(shader: sys::Shader) -> Option<Shader<'ctx>> {
+        // SAFETY: Caller guarantees the context is alive
+        if unsafe { low::is_shader_valid(shader) } {
+            Some(Shader { shader, _ctx: PhantomData })
+        } else {
+            None
+        }
+    }
+
+    /// [`Shader::from_sys()`] for a shader with at least one custom stage
+    ///
+    /// Raylib falls back to the default shader when compiling or linking fails; that is reported as [`None`],
+    /// because the default shader is unloaded by `close_window` rather than by [`Shader`].
+    ///
+    /// # Safety
+    ///
+    /// `shader` must be loaded into the context borrowed by `'ctx` and not unloaded elsewhere.
+    #[inline]
+    unsafe fn from_sys_custom<'ctx>(shader: sys::Shader) -> Option<Shader<'ctx>> {
+        // SAFETY: Caller guarantees the context is alive
+        if shader.id == unsafe { low::rlgl::rl_get_shader_id_default() } {
+            return None;
+        }
+        // SAFETY: Caller guarantees the context is alive and the shader is not unloaded elsewhere
+        unsafe { Self::from_sys(shader) }
+    }
+
+    #[inline]
+    pub(crate) const fn as_sys(&self) -> sys::Shader {
+        self.shader
+    }
+
+    /// Get shader uniform location, [`None`] if the shader has no active uniform named `uniform_name`
+    ///
+    /// # Panics
+    /// - [`IntoCStr::into_cstr()`] fails for `uniform_name`
+    #[inline]
+    pub fn get_location(&self, uniform_name: impl IntoCStr) -> Option<u32> {
+        let uniform_name = uniform_name.into_cstr().unwrap();
+        // SAFETY: The borrow of `WindowContext` proves the OpenGL context is still alive
+        let loc = unsafe { low::get_shader_location(self.shader, uniform_name.as_ref()) };
+        loc.0.try_into().ok()
+    }
+}
+
+/// Font, glyph atlas stored in GPU memory (VRAM)
+pub struct Font<'ctx> {
+    font: sys::Font,
+    _ctx: PhantomData<&'ctx WindowContext>,
+}
+
+impl Drop for Font<'_> {
+    /// Unload font from CPU and GPU memory
+    #[inline]
+    fn drop(&mut self) {
+        // SAFETY:
+        // - `Font` is only constructed from a valid, non-default font
+        // - The borrow of `WindowContext` proves the OpenGL context is still alive
+        unsafe {
+            low::unload_font(self.font);
+        }
+    }
+}
+
+impl Font<'_> {
+    #[inline]
+    pub(crate) const fn as_sys(&self) -> sys::Font {
+        self.font
+    }
+
+    /// Base size (default chars height)
+    #[inline]
+    pub const fn base_size(&self) -> u32 {
+        self.font.baseSize as u32
+    }
+
+    /// Number of glyph characters
+    #[inline]
+    pub const fn glyph_count(&self) -> usize {
+        self.font.glyphCount as usize
+    }
+}
+
+impl<'ctx> WindowInner<'ctx> {
+    /// Load texture from file into GPU memory (VRAM), [`None`] if loading failed
+    ///
+    /// # Panics
+    /// - [`IntoCStr::into_cstr()`] fails for `file_name`
+    #[inline]
+    pub fn load_texture(&self, file_name: impl IntoCStr) -> Option<Texture<'ctx>> {
+        let file_name = file_name.into_cstr().unwrap();
+        // SAFETY:
+        // - Existence of `WindowInner` proves window is initialized
+        // - The texture is owned by the returned `Texture` alone
+        unsafe { Texture::from_sys(low::load_texture(file_name.as_ref())) }
+    }
+
+    /// Load texture from image data, [`None`] if loading failed
+    #[inline]
+    pub fn load_texture_from_image(&self, image: &Image) -> Option<Texture<'ctx>> {
+        // SAFETY:
+        // - Existence of `WindowInner` proves window is initialized
+        // - `image` is valid and only read
+        // - The texture is owned by the returned `Texture` alone
+        unsafe { Texture::from_sys(low::load_texture_from_image(*image.as_sys())) }
+    }
+
+    /// Load texture for rendering (framebuffer), [`None`] if loading failed
+    #[inline]
+    pub fn load_render_texture(&self, width: u32, height: u32) -> Option<RenderTexture<'ctx>> {
+        // SAFETY: Existence of `WindowInner` proves window is initialized
+        let target = unsafe { low::load_render_texture(width, height) };
+        // SAFETY: Existence of `WindowInner` proves window is initialized
+        if unsafe { low::is_render_texture_valid(target) } {
+            Some(RenderTexture {
+                target,
+                texture: std::mem::ManuallyDrop::new(Texture { texture: target.texture, _ctx: PhantomData }),
+            })
+        } else {
+            None
+        }
+    }
+
+    /// Load shader from files and bind default locations, [`None`] if compiling or linking failed
+    ///
+    /// Stages that are [`None`] (such as `None::<&str>`) use Raylib's default shader.
+    ///
+    /// # Panics
+    /// - [`IntoCStr::into_cstr()`] fails for `vs_file_name` or `fs_file_name`
+    #[inline]
+    pub fn load_shader(&self, vs_file_name: Option<impl IntoCStr>, fs_file_name: Option<impl IntoCStr>) -> Option<Shader<'ctx>> {
+        let vs_file_name = vs_file_name.map(|path| path.into_cstr().unwrap());
+        let fs_file_name = fs_file_name.map(|path| path.into_cstr().unwrap());
+        // SAFETY:
+        // - Existence of `WindowInner` proves window is initialized
+        // - The shader is owned by the returned `Shader` alone
+        unsafe {
+            let shader = low::load_shader(vs_file_name.as_deref(), fs_file_name.as_deref());
+            if vs_file_name.is_some() || fs_file_name.is_some() {
+                Shader::from_sys_custom(shader)
+            } else {
+                Shader::from_sys(shader)
+            }
+        }
+    }
+
+    /// Load shader from code strings and bind default locations, [`None`] if compiling or linking failed
+    ///
+    /// Stages that are [`None`] use Raylib's default shader.
+    ///
+    /// # Panics
+    /// - [`IntoCStr::into_cstr()`] fails for `vs_code` or `fs_code`
+    #[inline]
+    pub fn load_shader_from_memory(&self, vs_code: Option<&str>, fs_code: Option<&str>) -> Option<Shader<'ctx>> {
+        let vs_code = vs_code.map(|code| code.into_cstr().unwrap());
+        let fs_code = fs_code.map(|code| code.into_cstr().unwrap());
+        // SAFETY:
+        // - Existence of `WindowInner` proves window is initialized
+        // - The shader is owned by the returned `Shader` alone
+        unsafe {
+            let shader = low::load_shader_from_memory(vs_code.as_deref(), fs_code.as_deref());
+            if vs_code.is_some() || fs_code.is_some() {
+                Shader::from_sys_custom(shader)
+            } else {
+                Shader::from_sys(shader)
+            }
+        }
+    }
+
+    /// Load font from file into GPU memory (VRAM), [`None`] if loading failed
+    ///
+    /// Raylib falls back to the default font when a file can't be read; that is reported as [`None`] too,
+    /// because the default font is unloaded by `close_window` rather than by [`Font`].
+    ///
+    /// # Panics
+    /// - [`IntoCStr::into_cstr()`] fails for `file_name`
+    #[inline]
+    pub fn load_font(&self, file_name: impl IntoCStr) -> Option<Font<'ctx>> {
+        let file_name = file_name.into_cstr().unwrap();
+        // SAFETY: Existence of `WindowInner` proves window is initialized
+        let font = unsafe { low::load_font(file_name.as_ref()) };
+        // SAFETY: Existence of `WindowInner` proves window is initialized
+        let default_texture = unsafe { low::get_font_default() }.texture.id;
+        // SAFETY: Existence of `WindowInner` proves window is initialized
+        if unsafe { low::is_font_valid(font) } && font.texture.id != default_texture {
+            Some(Font { font, _ctx: PhantomData })
+        } else {
+            None
+        }
+    }
+}
@@ -0,0 +1,181 @@
+//! Lock-free single-producer single-consumer ring buffer
+//!
+//! Used to move samples between Raylib's audio thread and the game thread without either side blocking.
+
+use std::{cell::UnsafeCell, mem::MaybeUninit, sync::{Arc, atomic::{AtomicU64, AtomicUsize, Ordering}}};
+
+struct Shared<T> {
+    slots: Box<[UnsafeCell<MaybeUninit<T>>]>,
+    /// `slots.len() - 1`, the capacity is always a power of two
+    mask: usize,
+    /// Total number of items pushed (wrapping)
+    head: AtomicUsize,
+    /// Total number of items popped (wrapping)
+    tail: AtomicUsize,
+    /// Number of items dropped because the buffer was full
+    overruns: AtomicU64,
+}
+
+// SAFETY: The producer only writes slots in `head..tail + capacity` and the consumer only reads slots in
+// `tail..head`. Publishing `head`/`tail` with release ordering hands each slot over to the other side.
+unsafe impl<T: Send> Sync for Shared<T> {}
+
+impl<T> Shared<T> {
+    #[inline]
+    fn capacity(&self) -> usize {
+        self.slots.len()
+    }
+
+    #[inline]
+    fn len(&self) -> usize {
+        self.head.load(Ordering::Acquire)
+            .wrapping_sub(self.tail.load(Ordering::Acquire))
+    }
+}
+
+/// Create a ring buffer that can hold at least `capacity` items
+///
+/// The capacity is rounded up to the next power of two.
+///
+/// # Panics
+///
+/// If the rounded capacity does not fit in a [`usize`].
+pub fn ring_buffer<T: Copy + Send>(capacity: usize) -> (RingProducer<T>, RingConsumer<T>) {
+    let capacity = capacity
+        .max(1)
+        .checked_next_power_of_two()
+        .expect("ring buffer capacity should fit in usize");
+    let shared = Arc::new(Shared {
+        slots: (0..capacity).map(|_| UnsafeCell::new(MaybeUninit::uninit())).collect(),
+        mask: capacity - 1,
+        head: AtomicUsize::new(0),
+        tail: AtomicUsize::new(0),
+        overruns: AtomicU64::new(0),
+    });
+    (RingProducer { shared: shared.clone() }, RingConsumer { shared })
+}
+
+/// Writing half of a [`ring_buffer()`]
+///
+/// Never blocks or allocates, so it is safe to use from the audio thread.
+pub struct RingProducer<T: Copy + Send> {
+    shared: Arc<Shared<T>>,
+}
+
+impl<T: Copy + Send> RingProducer<T> {
+    /// Maximum number of items the buffer can hold
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.shared.capacity()
+    }
+
+    /// Number of items that can be pushed before the buffer is full
+    #[inline]
+    pub fn free_len(&self) -> usize {
+        self.capacity() - self.shared.len()
+    }
+
+    /// Push as many items as fit, returning how many were pushed
+    ///
+    /// Items that do not fit are dropped (newest first) and counted in [`RingProducer::overruns()`].
+    pub fn push_slice(&mut self, items: &[T]) -> usize {
+        let head = self.shared.head.load(Ordering::Relaxed);
+        let tail = self.shared.tail.load(Ordering::Acquire);
+        let free = self.capacity() - head.wrapping_sub(tail);
+        let count = free.min(items.len());
+        for (i, item) in items[..count].iter().enumerate() {
+            let slot = &self.shared.slots[head.wrapping_add(i) & self.shared.mask];
+            // SAFETY: The slot is outside `tail..head`, so the consumer is not reading it
+            unsafe {
+                (*slot.get()).write(*item);
+            }
+        }
+        self.shared.head.store(head.wrapping_add(count), Ordering::Release);
+        let dropped = items.len() - count;
+        if dropped > 0 {
+            self.shared.overruns.fetch_add(dropped as u64, Ordering::Relaxed);
+        }
+        count
+    }
+
+    /// Push one item, returning `false` if the buffer was full
+    #[inline]
+    pub fn push(&mut self, item: T) -> bool {
+        self.push_slice(std::slice::from_ref(&item)) == 1
+    }
+
+    /// Number of items dropped because the buffer was full
+    #[inline]
+    pub fn overruns(&self) -> u64 {
+        self.shared.overruns.load(Ordering::Relaxed)
+    }
+}
+
+/// Reading half of a [`ring_buffer()`]
+pub struct RingConsumer<T: Copy + Send> {
+    shared: Arc<Shared<T>>,
+}
+
+impl<T: Copy + Send> RingConsumer<T> {
+    /// Maximum number of items the buffer can hold
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.shared.capacity()
+    }
+
+    /// Number of items waiting to be popped
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.shared.len()
+    }
+
+    /// Whether there are no items waiting to be popped
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Pop items into `out`, returning how many were written
+    pub fn pop_slice(&mut self, out: &mut [T]) -> usize {
+        let tail = self.shared.tail.load(Ordering::Relaxed);
+        let head = self.shared.head.load(Ordering::Acquire);
+        let count = head.wrapping_sub(tail).min(out.len());
+        for (i, item) in out[..count].iter_mut().enumerate() {
+            let slot = &self.shared.slots[tail.wrapping_add(i) & self.shared.mask];
+            // SAFETY: The slot is inside `tail..head`, so the producer has initialized it and is not writing it
+            *item = unsafe { (*slot.get()).assume_init() };
+        }
+        self.shared.tail.store(tail.wrapping_add(count), Ordering::Release);
+        count
+    }
+
+    /// Pop one item
+    #[inline]
+    pub fn pop(&mut self) -> Option<T> {
+        let tail = self.shared.tail.load(Ordering::Relaxed);
+        if self.shared.head.load(Ordering::Acquire) == tail {
+            return None;
+        }
+        let slot = &self.shared.slots[tail & self.shared.mask];
+        // SAFETY: The slot is inside `tail..head`, so the producer has initialized it and is not writing it
+        let item = unsafe { (*slot.get()).assume_init() };
+        self.shared.tail.store(tail.wrapping_add(1), Ordering::Release);
+        Some(item)
+    }
+
+    /// Discard up to `count` of the oldest items, returning how many were discarded
+    #[inline]
+    pub fn skip(&mut self, count: usize) -> usize {
+        let tail = self.shared.tail.load(Ordering::Relaxed);
+        let head = self.shared.head.load(Ordering::Acquire);
+        let count = head.wrapping_sub(tail).min(count);
+        self.shared.tail.store(tail.wrapping_add(count), Ordering::Release);
+        count
+    }
+
+    /// Number of items the producer dropped because the buffer was full
+    #[inline]
+    pub fn overruns(&self) -> u64 {
+        self.shared.overruns.load(Ordering::Relaxed)
+    }
+}
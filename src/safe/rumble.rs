@@ -0,0 +1,337 @@
+//! Gamepad vibration over time
+//!
+//! A [`RumblePattern`] is a list of keyframes giving the strength of both motors over time.
+//! A [`RumblePlayer`] layers the patterns being played for one gamepad and drives its motors each frame:
+//!
+//! ```ignore
+//! let mut rumble = RumblePlayer::new(RumbleBlend::Max);
+//! rumble.play(RumblePattern::engine());
+//! while !rl.should_close() {
+//!     if player_was_hit {
+//!         rumble.play(RumblePattern::hit(0.8));
+//!     }
+//!     let dt = rl.get_frame_duration();
+//!     if let Some(mut pad) = rl.gamepad(0) {
+//!         rumble.update(&mut pad, dt);
+//!     }
+//!     // ...
+//! }
+//! ```
+
+use super::*;
+
+/// How long each vibration sent by [`RumblePlayer::update()`] lasts, so the motors stop if updates stop
+const VIBRATION_HOLD: Duration = Duration::from_millis(100);
+
+/// Strength of both motors, each in `0.0..=1.0`
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Rumble {
+    /// Left motor, usually the low frequency one
+    pub left: f32,
+    /// Right motor, usually the high frequency one
+    pub right: f32,
+}
+
+impl Rumble {
+    /// Both motors stopped
+    pub const OFF: Self = Self::new(0.0, 0.0);
+
+    /// Rumble with the given strengths
+    #[inline]
+    pub const fn new(left: f32, right: f32) -> Self {
+        Self { left, right }
+    }
+
+    /// Check if both motors are stopped
+    #[inline]
+    pub fn is_off(self) -> bool {
+        self.left <= 0.0 && self.right <= 0.0
+    }
+
+    /// Interpolate linearly towards `other`
+    #[inline]
+    pub fn lerp(self, other: Self, amount: f32) -> Self {
+        Self {
+            left: self.left + (other.left - self.left) * amount,
+            right: self.right + (other.right - self.right) * amount,
+        }
+    }
+
+    /// Multiply both strengths
+    #[inline]
+    pub fn scale(self, gain: f32) -> Self {
+        Self::new(self.left * gain, self.right * gain)
+    }
+
+    /// Clamp both strengths to `0.0..=1.0`
+    #[inline]
+    pub fn clamped(self) -> Self {
+        Self::new(self.left.clamp(0.0, 1.0), self.right.clamp(0.0, 1.0))
+    }
+}
+
+/// How a keyframe is reached from the previous one
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum RumbleCurve {
+    /// Hold the previous strength, then jump at the keyframe
+    Step,
+    /// Constant rate of change
+    #[default]
+    Linear,
+    /// Start slowly and speed up
+    EaseIn,
+    /// Start quickly and slow down
+    EaseOut,
+    /// Start and end slowly
+    EaseInOut,
+}
+
+impl RumbleCurve {
+    /// Map progress through a segment, in `0.0..=1.0`, to interpolation amount
+    #[inline]
+    pub fn apply(self, t: f32) -> f32 {
+        let t = t.clamp(0.0, 1.0);
+        match self {
+            Self::Step => if t >= 1.0 { 1.0 } else { 0.0 },
+            Self::Linear => t,
+            Self::EaseIn => t * t,
+            Self::EaseOut => 1.0 - (1.0 - t) * (1.0 - t),
+            Self::EaseInOut => t * t * (3.0 - 2.0 * t),
+        }
+    }
+}
+
+/// Motor strengths at a point in a [`RumblePattern`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RumbleKeyframe {
+    /// Time since the start of the pattern
+    pub time: Duration,
+    /// Strength at `time`
+    pub rumble: Rumble,
+    /// How the strength changes from the previous keyframe
+    pub curve: RumbleCurve,
+}
+
+/// Motor strengths over time, built from keyframes
+///
+/// Before the first keyframe the pattern holds its strength; after the last one a looping pattern starts over
+/// and any other pattern is finished.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct RumblePattern {
+    keyframes: Vec<RumbleKeyframe>,
+    looping: bool,
+}
+
+impl RumblePattern {
+    /// A pattern with no keyframes, which is finished immediately
+    #[inline]
+    pub const fn new() -> Self {
+        Self {
+            keyframes: Vec::new(),
+            looping: false,
+        }
+    }
+
+    /// Add a keyframe
+    ///
+    /// # Panics
+    /// - `time` is before the last keyframe
+    pub fn keyframe(mut self, time: Duration, rumble: Rumble, curve: RumbleCurve) -> Self {
+        assert!(self.keyframes.last().is_none_or(|last| last.time <= time), "keyframes should be in order");
+        self.keyframes.push(RumbleKeyframe { time, rumble, curve });
+        self
+    }
+
+    /// Start over after the last keyframe, until stopped
+    #[inline]
+    pub fn looping(mut self) -> Self {
+        self.looping = true;
+        self
+    }
+
+    /// The keyframes, in order
+    #[inline]
+    pub fn keyframes(&self) -> &[RumbleKeyframe] {
+        &self.keyframes
+    }
+
+    /// Check if the pattern starts over after the last keyframe
+    #[inline]
+    pub const fn is_looping(&self) -> bool {
+        self.looping
+    }
+
+    /// Time of the last keyframe
+    #[inline]
+    pub fn duration(&self) -> Duration {
+        self.keyframes.last().map_or(Duration::ZERO, |last| last.time)
+    }
+
+    /// Check if the pattern has ended at `time`; never true for looping patterns with keyframes
+    #[inline]
+    pub fn is_finished(&self, time: Duration) -> bool {
+        self.keyframes.is_empty() || !self.looping && time >= self.duration()
+    }
+
+    /// Motor strengths `time` after the start of the pattern
+    pub fn sample(&self, time: Duration) -> Rumble {
+        let (Some(first), Some(last)) = (self.keyframes.first(), self.keyframes.last()) else {
+            return Rumble::OFF;
+        };
+        let time = if !self.looping {
+            if time >= last.time {
+                return Rumble::OFF;
+            }
+            time
+        } else if last.time.is_zero() {
+            return first.rumble;
+        } else {
+            Duration::from_nanos((time.as_nanos() % last.time.as_nanos()) as u64)
+        };
+        // First keyframe after `time`
+        let next = self.keyframes.partition_point(|keyframe| keyframe.time <= time);
+        let Some(prev) = next.checked_sub(1).map(|i| self.keyframes[i]) else {
+            return first.rumble;
+        };
+        let Some(next) = self.keyframes.get(next) else {
+            return prev.rumble;
+        };
+        let t = (time - prev.time).as_secs_f32() / (next.time - prev.time).as_secs_f32();
+        prev.rumble.lerp(next.rumble, next.curve.apply(t))
+    }
+
+    /// A single impact: full `strength` fading out quickly
+    pub fn hit(strength: f32) -> Self {
+        Self::new()
+            .keyframe(Duration::ZERO, Rumble::new(strength, strength), RumbleCurve::Step)
+            .keyframe(Duration::from_millis(180), Rumble::OFF, RumbleCurve::EaseOut)
+    }
+
+    /// Two beats a second, until stopped
+    pub fn heartbeat() -> Self {
+        Self::new()
+            .keyframe(Duration::ZERO, Rumble::OFF, RumbleCurve::Step)
+            .keyframe(Duration::from_millis(60), Rumble::new(0.8, 0.3), RumbleCurve::EaseIn)
+            .keyframe(Duration::from_millis(160), Rumble::OFF, RumbleCurve::EaseOut)
+            .keyframe(Duration::from_millis(260), Rumble::new(0.5, 0.2), RumbleCurve::EaseIn)
+            .keyframe(Duration::from_millis(400), Rumble::OFF, RumbleCurve::EaseOut)
+            .keyframe(Duration::from_millis(1000), Rumble::OFF, RumbleCurve::Step)
+            .looping()
+    }
+
+    /// A low idle rumble with a slight wobble, until stopped
+    pub fn engine() -> Self {
+        Self::new()
+            .keyframe(Duration::ZERO, Rumble::new(0.3, 0.05), RumbleCurve::Step)
+            .keyframe(Duration::from_millis(60), Rumble::new(0.4, 0.12), RumbleCurve::EaseInOut)
+            .keyframe(Duration::from_millis(120), Rumble::new(0.3, 0.05), RumbleCurve::EaseInOut)
+            .looping()
+    }
+}
+
+/// How a [`RumblePlayer`] combines the patterns it plays
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum RumbleBlend {
+    /// The strongest pattern wins, per motor
+    #[default]
+    Max,
+    /// Strengths add up, up to `1.0`
+    Sum,
+}
+
+/// A pattern being played by a [`RumblePlayer`]
+#[derive(Debug, Clone, PartialEq)]
+struct RumbleLayer {
+    pattern: RumblePattern,
+    gain: f32,
+    elapsed: Duration,
+}
+
+/// Patterns playing on one gamepad
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct RumblePlayer {
+    blend: RumbleBlend,
+    layers: Vec<RumbleLayer>,
+    current: Rumble,
+}
+
+impl RumblePlayer {
+    /// A player with nothing playing
+    #[inline]
+    pub const fn new(blend: RumbleBlend) -> Self {
+        Self {
+            blend,
+            layers: Vec::new(),
+            current: Rumble::OFF,
+        }
+    }
+
+    /// Start playing a pattern on top of the ones already playing
+    #[inline]
+    pub fn play(&mut self, pattern: RumblePattern) {
+        self.play_scaled(pattern, 1.0);
+    }
+
+    /// Start playing a pattern with its strengths multiplied by `gain`
+    pub fn play_scaled(&mut self, pattern: RumblePattern, gain: f32) {
+        if !pattern.is_finished(Duration::ZERO) {
+            self.layers.push(RumbleLayer { pattern, gain, elapsed: Duration::ZERO });
+        }
+    }
+
+    /// Stop every pattern
+    #[inline]
+    pub fn stop(&mut self) {
+        self.layers.clear();
+    }
+
+    /// Check if any pattern is playing
+    #[inline]
+    pub fn is_playing(&self) -> bool {
+        !self.layers.is_empty()
+    }
+
+    /// Number of patterns playing
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.layers.len()
+    }
+
+    /// Check if no patterns are playing
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.layers.is_empty()
+    }
+
+    /// Combined strength as of the last advance
+    #[inline]
+    pub const fn current(&self) -> Rumble {
+        self.current
+    }
+
+    /// Combined strength at the current time, without advancing
+    pub fn sample(&self) -> Rumble {
+        let samples = self.layers.iter()
+            .map(|layer| layer.pattern.sample(layer.elapsed).scale(layer.gain));
+        match self.blend {
+            RumbleBlend::Max => samples.fold(Rumble::OFF, |a, b| Rumble::new(a.left.max(b.left), a.right.max(b.right))),
+            RumbleBlend::Sum => samples.fold(Rumble::OFF, |a, b| Rumble::new(a.left + b.left, a.right + b.right)),
+        }.clamped()
+    }
+
+    /// Move time forward, drop finished patterns and return the combined strength
+    pub fn advance(&mut self, frame_time: Duration) -> Rumble {
+        for layer in &mut self.layers {
+            layer.elapsed += frame_time;
+        }
+        self.layers.retain(|layer| !layer.pattern.is_finished(layer.elapsed));
+        self.current = self.sample();
+        self.current
+    }
+
+    /// Advance by the frame's duration and set the gamepad's vibration; call once per frame
+    pub fn update(&mut self, gamepad: &mut Gamepad<'_>, frame_time: Duration) {
+        let Rumble { left, right } = self.advance(frame_time);
+        gamepad.set_vibration(left, right, VIBRATION_HOLD);
+    }
+}
@@ -0,0 +1,245 @@
+//! Frame-based scripting with `async`
+//!
+//! Cutscenes and tutorials are often a chain of "wait, then wait for a key, then wait some more".
+//! [`Window::run_async()`] drives an `async` script once per frame, so such a chain can be written
+//! as a plain `async fn` instead of a state machine:
+//!
+//! ```ignore
+//! let step = Rc::new(Cell::new(0));
+//! let script_step = step.clone();
+//! rl.run_async(
+//!     |frames| async move {
+//!         frames.wait(Duration::from_secs(2)).await;
+//!         script_step.set(1); // "Press space to jump"
+//!         frames.key_pressed(KeyboardKey::Space).await;
+//!         script_step.set(2);
+//!         frames.wait_frames(30).await;
+//!     },
+//!     |_rl, d, _m| {
+//!         d.clear_background(Color::RAYWHITE);
+//!         draw_tutorial(d, step.get());
+//!     },
+//! );
+//! ```
+//!
+//! Every pending future is polled once per frame, before drawing, so futures from elsewhere work too
+//! as long as they don't rely on being woken from another thread.
+
+use std::{cell::RefCell, pin::{Pin, pin}, rc::Rc, task::{Context, Poll, Waker}};
+use super::*;
+
+/// A task started by [`Frames::spawn()`]
+type Task = Pin<Box<dyn Future<Output = ()>>>;
+
+#[derive(Default)]
+struct FrameState {
+    frame: u64,
+    elapsed: Duration,
+    /// Keys awaited during the current frame, sampled at the start of the next
+    watched_keys: Vec<KeyboardKey>,
+    watched_buttons: Vec<MouseButton>,
+    /// Watched keys that were pressed this frame
+    pressed_keys: Vec<KeyboardKey>,
+    pressed_buttons: Vec<MouseButton>,
+    spawned: Vec<Task>,
+}
+
+/// Handle passed to a [`Window::run_async()`] script, for awaiting frames, time and input
+///
+/// Cloning it is cheap, so each `async` block can own one.
+#[derive(Clone, Default)]
+pub struct Frames(Rc<RefCell<FrameState>>);
+
+impl Frames {
+    /// Start the next frame, sampling the watched input
+    fn begin_frame(&self, rl: &WindowInner<'_>) {
+        let mut state = self.0.borrow_mut();
+        let state = &mut *state;
+        state.frame += 1;
+        state.elapsed = rl.get_elapsed();
+        state.pressed_keys.clear();
+        state.pressed_keys.extend(state.watched_keys.drain(..).filter(|&key| rl.is_key_pressed(key)));
+        state.pressed_buttons.clear();
+        state.pressed_buttons.extend(state.watched_buttons.drain(..).filter(|&button| rl.is_mouse_button_pressed(button)));
+    }
+
+    /// Tasks spawned since the last call
+    fn take_spawned(&self) -> Vec<Task> {
+        std::mem::take(&mut self.0.borrow_mut().spawned)
+    }
+
+    /// Number of the current frame, starting at 1
+    #[inline]
+    pub fn frame(&self) -> u64 {
+        self.0.borrow().frame
+    }
+
+    /// Time since the window was opened, as of the start of the current frame
+    #[inline]
+    pub fn elapsed(&self) -> Duration {
+        self.0.borrow().elapsed
+    }
+
+    /// Wait until the next frame
+    #[inline]
+    pub fn next_frame(&self) -> WaitFrames {
+        self.wait_frames(1)
+    }
+
+    /// Wait until `count` frames have passed
+    #[inline]
+    pub fn wait_frames(&self, count: u64) -> WaitFrames {
+        WaitFrames { frames: self.clone(), count, start: None }
+    }
+
+    /// Wait until `duration` has passed, checked once per frame
+    ///
+    /// Time is measured from the start of the frame the future is first polled in.
+    #[inline]
+    pub fn wait(&self, duration: Duration) -> Wait {
+        Wait { frames: self.clone(), duration, deadline: None }
+    }
+
+    /// Wait until `key` is pressed in a later frame
+    ///
+    /// A press in the frame the future is first polled in does not count, so awaiting the same key
+    /// twice in a row waits for two presses.
+    #[inline]
+    pub fn key_pressed(&self, key: KeyboardKey) -> KeyPressed {
+        KeyPressed { frames: self.clone(), key, start: None }
+    }
+
+    /// Wait until `button` is pressed in a later frame
+    ///
+    /// A press in the frame the future is first polled in does not count, see [`Frames::key_pressed()`].
+    #[inline]
+    pub fn mouse_clicked(&self, button: MouseButton) -> MouseClicked {
+        MouseClicked { frames: self.clone(), button, start: None }
+    }
+
+    /// Run `task` alongside the script, starting this frame if spawned by the script itself, or the next frame otherwise
+    ///
+    /// Spawned tasks are dropped when the script passed to [`Window::run_async()`] finishes.
+    #[inline]
+    pub fn spawn(&self, task: impl Future<Output = ()> + 'static) {
+        self.0.borrow_mut().spawned.push(Box::pin(task));
+    }
+}
+
+/// Future returned by [`Frames::wait_frames()`] and [`Frames::next_frame()`]
+#[must_use = "futures do nothing unless awaited"]
+pub struct WaitFrames {
+    frames: Frames,
+    count: u64,
+    start: Option<u64>,
+}
+
+impl Future for WaitFrames {
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<()> {
+        let this = self.get_mut();
+        let frame = this.frames.frame();
+        let start = *this.start.get_or_insert(frame);
+        if frame - start >= this.count { Poll::Ready(()) } else { Poll::Pending }
+    }
+}
+
+/// Future returned by [`Frames::wait()`]
+#[must_use = "futures do nothing unless awaited"]
+pub struct Wait {
+    frames: Frames,
+    duration: Duration,
+    deadline: Option<Duration>,
+}
+
+impl Future for Wait {
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<()> {
+        let this = self.get_mut();
+        let elapsed = this.frames.elapsed();
+        let deadline = *this.deadline.get_or_insert(elapsed + this.duration);
+        if elapsed >= deadline { Poll::Ready(()) } else { Poll::Pending }
+    }
+}
+
+/// Future returned by [`Frames::key_pressed()`]
+#[must_use = "futures do nothing unless awaited"]
+pub struct KeyPressed {
+    frames: Frames,
+    key: KeyboardKey,
+    start: Option<u64>,
+}
+
+impl Future for KeyPressed {
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<()> {
+        let this = self.get_mut();
+        let mut state = this.frames.0.borrow_mut();
+        let start = *this.start.get_or_insert(state.frame);
+        if state.frame > start && state.pressed_keys.contains(&this.key) {
+            return Poll::Ready(());
+        }
+        if !state.watched_keys.contains(&this.key) {
+            state.watched_keys.push(this.key);
+        }
+        Poll::Pending
+    }
+}
+
+/// Future returned by [`Frames::mouse_clicked()`]
+#[must_use = "futures do nothing unless awaited"]
+pub struct MouseClicked {
+    frames: Frames,
+    button: MouseButton,
+    start: Option<u64>,
+}
+
+impl Future for MouseClicked {
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<()> {
+        let this = self.get_mut();
+        let mut state = this.frames.0.borrow_mut();
+        let start = *this.start.get_or_insert(state.frame);
+        if state.frame > start && state.pressed_buttons.contains(&this.button) {
+            return Poll::Ready(());
+        }
+        if !state.watched_buttons.contains(&this.button) {
+            state.watched_buttons.push(this.button);
+        }
+        Poll::Pending
+    }
+}
+
+impl<'ctx> Window<'ctx> {
+    /// Draw frames while running an `async` script, until it finishes or the window should close
+    ///
+    /// Each frame the script and the tasks it [spawned](Frames::spawn) are polled once, then `f` draws the frame.
+    ///
+    /// Returns the script's output, [`None`] if the window should close first.
+    pub fn run_async<F: Future>(
+        &mut self,
+        script: impl FnOnce(Frames) -> F,
+        mut f: impl FnMut(&mut WindowInner<'ctx>, &mut Drawing, &mut BaseDrawMode),
+    ) -> Option<F::Output> {
+        let frames = Frames::default();
+        let mut main = pin!(script(frames.clone()));
+        let mut tasks = Vec::new();
+        let mut cx = Context::from_waker(Waker::noop());
+        while !self.should_close() {
+            frames.begin_frame(self);
+            if let Poll::Ready(output) = main.as_mut().poll(&mut cx) {
+                return Some(output);
+            }
+            tasks.extend(frames.take_spawned());
+            tasks.retain_mut(|task: &mut Task| task.as_mut().poll(&mut cx).is_pending());
+            // Tasks spawned by tasks start next frame
+            tasks.extend(frames.take_spawned());
+            self.draw(|rl, d, m| f(rl, d, m));
+        }
+        None
+    }
+}
@@ -0,0 +1,177 @@
+//! Feeding an [`AudioStream`] from another thread
+//!
+//! Raylib streams must be updated from the thread that owns them. A [`StreamProducer`] can be sent to
+//! a decoding or networking thread and pushes samples into a [lock-free ring buffer](super::ring_buffer),
+//! which [`QueuedAudioStream::feed()`] drains into the stream once per frame.
+
+use std::{ops::Deref, sync::{Arc, atomic::{AtomicU64, Ordering}}, time::Duration};
+use super::*;
+
+/// Smallest sub-buffer Raylib is asked to allocate, in frames
+const MIN_BUFFER_FRAMES: usize = 64;
+
+/// Counters shared by both halves of a queue
+#[derive(Default)]
+struct QueueCounters {
+    underruns: AtomicU64,
+    overrun_frames: AtomicU64,
+}
+
+/// Sending half of a [`QueuedAudioStream`]
+///
+/// Pushes interleaved 32-bit float samples without blocking or allocating.
+pub struct StreamProducer {
+    ring: RingProducer<f32>,
+    channels: usize,
+    counters: Arc<QueueCounters>,
+}
+
+impl StreamProducer {
+    /// Number of interleaved channels expected by [`StreamProducer::push()`]
+    #[inline]
+    pub const fn channels(&self) -> usize {
+        self.channels
+    }
+
+    /// Number of frames that can be pushed before the queue is full
+    #[inline]
+    pub fn free_frames(&self) -> usize {
+        self.ring.free_len() / self.channels
+    }
+
+    /// Push interleaved samples, returning how many frames were queued
+    ///
+    /// Frames that do not fit are dropped and counted in [`StreamProducer::overrun_frames()`].
+    /// Producers that must not lose audio should wait until [`StreamProducer::free_frames()`] is large enough.
+    ///
+    /// # Panics
+    ///
+    /// If `samples` does not contain whole frames.
+    pub fn push(&mut self, samples: &[f32]) -> usize {
+        assert!(samples.len().is_multiple_of(self.channels), "samples should contain whole frames");
+        let frames = self.free_frames().min(samples.len() / self.channels);
+        self.ring.push_slice(&samples[..frames * self.channels]);
+        let dropped = samples.len() / self.channels - frames;
+        if dropped > 0 {
+            self.counters.overrun_frames.fetch_add(dropped as u64, Ordering::Relaxed);
+        }
+        frames
+    }
+
+    /// Number of frames dropped because the queue was full
+    #[inline]
+    pub fn overrun_frames(&self) -> u64 {
+        self.counters.overrun_frames.load(Ordering::Relaxed)
+    }
+
+    /// Number of times the stream needed data while the queue was short of a full buffer
+    #[inline]
+    pub fn underruns(&self) -> u64 {
+        self.counters.underruns.load(Ordering::Relaxed)
+    }
+}
+
+/// An [`AudioStream`] of 32-bit float samples fed by a [`StreamProducer`]
+///
+/// Dereferences to the stream for playback controls and analyzer taps.
+pub struct QueuedAudioStream<'a> {
+    stream: AudioStream<'a>,
+    ring: RingConsumer<f32>,
+    buffer_frames: usize,
+    scratch: Vec<f32>,
+    counters: Arc<QueueCounters>,
+}
+
+impl<'a> Deref for QueuedAudioStream<'a> {
+    type Target = AudioStream<'a>;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        &self.stream
+    }
+}
+
+impl<'a> QueuedAudioStream<'a> {
+    /// Load a 32-bit float stream and the queue feeding it
+    ///
+    /// `latency` is the target delay between a sample being pushed and it being played.
+    /// Half of it is spent in Raylib's two stream buffers, the rest bounds how far ahead the producer may run.
+    ///
+    /// Returns [`None`] if the stream could not be loaded, see [`AudioStream::load()`].
+    pub fn load(
+        device: &'a AudioDevice,
+        sample_rate: u32,
+        channels: u32,
+        latency: Duration,
+    ) -> Option<(Self, StreamProducer)> {
+        let latency_frames = (latency.as_secs_f64() * f64::from(sample_rate)) as usize;
+        let buffer_frames = (latency_frames / 4).max(MIN_BUFFER_FRAMES);
+        let stream = AudioStream::load_with_buffer_size(device, sample_rate, 32, channels, buffer_frames)?;
+        let channels = channels as usize;
+        let (producer, consumer) = ring_buffer(latency_frames.max(2 * buffer_frames) * channels);
+        let counters = Arc::new(QueueCounters::default());
+        Some((
+            Self {
+                stream,
+                ring: consumer,
+                buffer_frames,
+                scratch: vec![0.0; buffer_frames * channels],
+                counters: counters.clone(),
+            },
+            StreamProducer {
+                ring: producer,
+                channels,
+                counters,
+            },
+        ))
+    }
+
+    /// Number of frames in each of the stream's buffers
+    #[inline]
+    pub const fn buffer_frames(&self) -> usize {
+        self.buffer_frames
+    }
+
+    /// Number of frames waiting in the queue
+    #[inline]
+    pub fn queued_frames(&self) -> usize {
+        self.ring.len() / self.stream.channels() as usize
+    }
+
+    /// Move queued samples into every stream buffer that has been played, call once per frame
+    ///
+    /// A buffer that can't be filled completely is padded with silence and counted as an underrun
+    /// while the stream is playing. Before playback starts this pre-fills the stream.
+    pub fn feed(&mut self) {
+        let channels = self.stream.channels() as usize;
+        while self.stream.is_processed() {
+            let frames = (self.ring.len() / channels).min(self.buffer_frames);
+            if frames > 0 {
+                let samples = &mut self.scratch[..frames * channels];
+                self.ring.pop_slice(samples);
+                // SAFETY: `f32` has no padding and every byte pattern is a valid `u8`
+                let bytes = unsafe { std::slice::from_raw_parts(samples.as_ptr().cast::<u8>(), size_of_val(samples)) };
+                // Raylib pads a partially updated buffer with silence
+                self.stream.update(bytes);
+            }
+            if frames < self.buffer_frames {
+                if self.stream.is_playing() {
+                    self.counters.underruns.fetch_add(1, Ordering::Relaxed);
+                }
+                break;
+            }
+        }
+    }
+
+    /// Number of frames dropped because the queue was full
+    #[inline]
+    pub fn overrun_frames(&self) -> u64 {
+        self.counters.overrun_frames.load(Ordering::Relaxed)
+    }
+
+    /// Number of times the stream needed data while the queue was short of a full buffer
+    #[inline]
+    pub fn underruns(&self) -> u64 {
+        self.counters.underruns.load(Ordering::Relaxed)
+    }
+}
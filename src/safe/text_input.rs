@@ -0,0 +1,554 @@
+//! Single-line text editing
+//!
+//! A [`TextInput`] is a plain value holding the text, caret, selection and undo history.
+//! [`TextInput::update()`] feeds it the window's typed characters, editing keys and clipboard once per frame,
+//! and every edit is also available as a method, so it can be driven and tested without a window:
+//!
+//! ```ignore
+//! let mut name = TextInput::new()
+//!     .with_max_len(16)
+//!     .with_filter(|c| c.is_alphanumeric() || c == ' ');
+//! while !rl.should_close() {
+//!     name.update(&mut rl);
+//!     rl.draw(|rl, d, _m| {
+//!         d.draw_text(name.text(), 10, 10, 20, Color::BLACK);
+//!         let caret_x = 10 + rl.measure_text(&name.text()[..name.caret()], 20);
+//!         d.draw_rectangle(caret_x, 10, 2, 20, Color::BLACK);
+//!     });
+//! }
+//! ```
+//!
+//! Positions are byte offsets into the text, and always lie on a grapheme cluster boundary,
+//! so the caret never splits an accented letter or an emoji sequence.
+
+use std::ops::Range;
+use super::*;
+
+/// Number of undo steps kept
+const UNDO_LIMIT: usize = 100;
+
+/// Where [`TextInput::move_caret()`] and [`TextInput::delete()`] go from the caret
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Movement {
+    /// One grapheme cluster left
+    Left,
+    /// One grapheme cluster right
+    Right,
+    /// To the start of the current or previous word
+    WordLeft,
+    /// To the end of the current or next word
+    WordRight,
+    /// To the start of the text
+    Home,
+    /// To the end of the text
+    End,
+}
+
+/// Kind of the last edit, so that runs of typing or deleting undo as one step
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum EditKind {
+    None,
+    Typing,
+    Deleting,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Snapshot {
+    text: String,
+    caret: usize,
+    anchor: usize,
+}
+
+/// An editable line of text with a caret, selection and undo history
+#[derive(Debug, Clone)]
+pub struct TextInput {
+    text: String,
+    caret: usize,
+    /// Other end of the selection, equal to `caret` when nothing is selected
+    anchor: usize,
+    max_len: Option<usize>,
+    filter: Option<fn(char) -> bool>,
+    undo: Vec<Snapshot>,
+    redo: Vec<Snapshot>,
+    last_edit: EditKind,
+}
+
+impl Default for TextInput {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TextInput {
+    /// Empty text accepting any printable character
+    #[inline]
+    pub const fn new() -> Self {
+        Self {
+            text: String::new(),
+            caret: 0,
+            anchor: 0,
+            max_len: None,
+            filter: None,
+            undo: Vec::new(),
+            redo: Vec::new(),
+            last_edit: EditKind::None,
+        }
+    }
+
+    /// Limit the text to `max_len` characters
+    #[inline]
+    pub fn with_max_len(mut self, max_len: usize) -> Self {
+        self.max_len = Some(max_len);
+        self
+    }
+
+    /// Only accept characters for which `filter` returns `true`
+    ///
+    /// Control characters such as newlines are never accepted.
+    #[inline]
+    pub fn with_filter(mut self, filter: fn(char) -> bool) -> Self {
+        self.filter = Some(filter);
+        self
+    }
+
+    /// Start with `text`, filtered, with the caret at the end
+    #[inline]
+    pub fn with_text(mut self, text: &str) -> Self {
+        self.insert_filtered(text);
+        self.anchor = self.caret;
+        self
+    }
+
+    /// The text
+    #[inline]
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// Take the text
+    #[inline]
+    pub fn into_string(self) -> String {
+        self.text
+    }
+
+    /// Byte offset of the caret
+    #[inline]
+    pub const fn caret(&self) -> usize {
+        self.caret
+    }
+
+    /// Byte range of the selection, [`None`] if nothing is selected
+    #[inline]
+    pub fn selection(&self) -> Option<Range<usize>> {
+        (self.caret != self.anchor).then(|| self.caret.min(self.anchor)..self.caret.max(self.anchor))
+    }
+
+    /// The selected text, empty if nothing is selected
+    #[inline]
+    pub fn selected_text(&self) -> &str {
+        self.selection().map_or("", |range| &self.text[range])
+    }
+
+    /// Replace the text, as one undo step, with the caret at the end
+    pub fn set_text(&mut self, text: &str) {
+        self.checkpoint(EditKind::None);
+        self.text.clear();
+        self.caret = 0;
+        self.insert_filtered(text);
+        self.anchor = self.caret;
+    }
+
+    /// Remove all text, as one undo step
+    #[inline]
+    pub fn clear(&mut self) {
+        self.set_text("");
+    }
+
+    /// Select the whole text
+    #[inline]
+    pub fn select_all(&mut self) {
+        self.anchor = 0;
+        self.caret = self.text.len();
+        self.last_edit = EditKind::None;
+    }
+
+    /// Clear the selection, leaving the caret where it is
+    #[inline]
+    pub fn deselect(&mut self) {
+        self.anchor = self.caret;
+    }
+
+    /// Byte offset that `movement` leads to from the caret
+    fn target(&self, movement: Movement) -> usize {
+        match movement {
+            Movement::Left => prev_boundary(&self.text, self.caret),
+            Movement::Right => next_boundary(&self.text, self.caret),
+            Movement::WordLeft => {
+                let target = word_left(&self.text, self.caret);
+                if is_boundary(&self.text, target) { target } else { prev_boundary(&self.text, target) }
+            }
+            Movement::WordRight => {
+                let target = word_right(&self.text, self.caret);
+                if is_boundary(&self.text, target) { target } else { next_boundary(&self.text, target) }
+            }
+            Movement::Home => 0,
+            Movement::End => self.text.len(),
+        }
+    }
+
+    /// Move the caret, extending the selection if `select` is `true`
+    ///
+    /// Without `select`, [`Movement::Left`] and [`Movement::Right`] collapse a selection to its start or end.
+    pub fn move_caret(&mut self, movement: Movement, select: bool) {
+        self.last_edit = EditKind::None;
+        self.caret = match (self.selection(), movement, select) {
+            (Some(range), Movement::Left, false) => range.start,
+            (Some(range), Movement::Right, false) => range.end,
+            _ => self.target(movement),
+        };
+        if !select {
+            self.anchor = self.caret;
+        }
+    }
+
+    /// Delete the selection, or the text between the caret and where `movement` leads
+    ///
+    /// Backspace is [`Movement::Left`] and Delete is [`Movement::Right`].
+    /// Returns whether anything was deleted.
+    pub fn delete(&mut self, movement: Movement) -> bool {
+        let range = self.selection().unwrap_or_else(|| {
+            let target = self.target(movement);
+            self.caret.min(target)..self.caret.max(target)
+        });
+        if range.is_empty() {
+            return false;
+        }
+        self.checkpoint(EditKind::Deleting);
+        self.text.replace_range(range.clone(), "");
+        self.caret = range.start;
+        self.anchor = range.start;
+        true
+    }
+
+    /// Type `text` at the caret, replacing the selection
+    ///
+    /// Characters rejected by the filter are skipped, and the rest is cut off at the length limit.
+    /// Returns whether anything changed.
+    pub fn insert(&mut self, text: &str) -> bool {
+        self.replace_selection(text, EditKind::Typing)
+    }
+
+    /// Paste `text` at the caret as its own undo step, replacing the selection
+    ///
+    /// Returns whether anything changed.
+    #[inline]
+    pub fn paste(&mut self, text: &str) -> bool {
+        self.replace_selection(text, EditKind::None)
+    }
+
+    /// The selected text to put on the clipboard, [`None`] if nothing is selected
+    #[inline]
+    pub fn copy(&self) -> Option<&str> {
+        self.selection().map(|range| &self.text[range])
+    }
+
+    /// Remove the selected text and return it for the clipboard, [`None`] if nothing is selected
+    pub fn cut(&mut self) -> Option<String> {
+        let range = self.selection()?;
+        self.checkpoint(EditKind::None);
+        let cut = self.text.drain(range.clone()).collect();
+        self.caret = range.start;
+        self.anchor = range.start;
+        Some(cut)
+    }
+
+    /// Whether there is an edit to undo
+    #[inline]
+    pub fn can_undo(&self) -> bool {
+        !self.undo.is_empty()
+    }
+
+    /// Whether there is an undone edit to redo
+    #[inline]
+    pub fn can_redo(&self) -> bool {
+        !self.redo.is_empty()
+    }
+
+    /// Revert the last edit, returning whether there was one
+    pub fn undo(&mut self) -> bool {
+        let Some(snapshot) = self.undo.pop() else {
+            return false;
+        };
+        let current = self.restore(snapshot);
+        self.redo.push(current);
+        true
+    }
+
+    /// Reapply the last undone edit, returning whether there was one
+    pub fn redo(&mut self) -> bool {
+        let Some(snapshot) = self.redo.pop() else {
+            return false;
+        };
+        let current = self.restore(snapshot);
+        self.undo.push(current);
+        true
+    }
+
+    /// Replace the current state with `snapshot`, returning the current state
+    fn restore(&mut self, snapshot: Snapshot) -> Snapshot {
+        self.last_edit = EditKind::None;
+        let current = self.snapshot();
+        self.text = snapshot.text;
+        self.caret = snapshot.caret;
+        self.anchor = snapshot.anchor;
+        current
+    }
+
+    fn snapshot(&self) -> Snapshot {
+        Snapshot {
+            text: self.text.clone(),
+            caret: self.caret,
+            anchor: self.anchor,
+        }
+    }
+
+    /// Save an undo step before an edit of `kind`, unless it continues a run of the same kind
+    fn checkpoint(&mut self, kind: EditKind) {
+        if kind == EditKind::None || kind != self.last_edit {
+            if self.undo.len() == UNDO_LIMIT {
+                self.undo.remove(0);
+            }
+            self.undo.push(self.snapshot());
+        }
+        self.redo.clear();
+        self.last_edit = kind;
+    }
+
+    fn replace_selection(&mut self, text: &str, kind: EditKind) -> bool {
+        let selection = self.selection();
+        let accepted = text.chars().any(|c| self.accepts(c));
+        if selection.is_none() && !accepted {
+            return false;
+        }
+        // Replacing a selection starts a new undo step even while typing
+        self.checkpoint(if selection.is_some() { EditKind::None } else { kind });
+        self.last_edit = kind;
+        if let Some(range) = selection {
+            self.text.replace_range(range.clone(), "");
+            self.caret = range.start;
+        }
+        self.insert_filtered(text);
+        self.anchor = self.caret;
+        true
+    }
+
+    fn accepts(&self, c: char) -> bool {
+        !c.is_control() && self.filter.is_none_or(|filter| filter(c))
+    }
+
+    /// Insert the accepted characters of `text` at the caret, up to the length limit
+    fn insert_filtered(&mut self, text: &str) {
+        let room = self.max_len.map_or(usize::MAX, |max_len| max_len.saturating_sub(self.text.chars().count()));
+        let accepted: String = text.chars()
+            .filter(|&c| self.accepts(c))
+            .take(room)
+            .collect();
+        self.text.insert_str(self.caret, &accepted);
+        self.caret += accepted.len();
+        // Cutting off at the limit may have split a grapheme cluster
+        if !is_boundary(&self.text, self.caret) {
+            self.caret = next_boundary(&self.text, self.caret);
+        }
+    }
+
+    /// Apply this frame's typed characters and editing keys
+    ///
+    /// Handles arrows, Home, End, Backspace and Delete, with Shift to select and Ctrl (or Cmd) to
+    /// move by words, plus Ctrl+A, Ctrl+C, Ctrl+X, Ctrl+V, Ctrl+Z and Ctrl+Y (or Ctrl+Shift+Z)
+    /// through the system clipboard.
+    ///
+    /// This drains the character queue of [`WindowInner::get_char_pressed()`].
+    pub fn update(&mut self, rl: &mut WindowInner<'_>) {
+        use KeyboardKey as Key;
+        let held = |keys: &[Key]| keys.iter().any(|&key| rl.is_key_down(key));
+        let command = held(&[Key::LeftControl, Key::RightControl, Key::LeftSuper, Key::RightSuper]);
+        let shift = held(&[Key::LeftShift, Key::RightShift]);
+        let pressed = |key| rl.is_key_pressed(key) || rl.is_key_pressed_repeat(key);
+
+        let (left, right) = if command {
+            (Movement::WordLeft, Movement::WordRight)
+        } else {
+            (Movement::Left, Movement::Right)
+        };
+        if pressed(Key::Left) {
+            self.move_caret(left, shift);
+        }
+        if pressed(Key::Right) {
+            self.move_caret(right, shift);
+        }
+        if pressed(Key::Home) {
+            self.move_caret(Movement::Home, shift);
+        }
+        if pressed(Key::End) {
+            self.move_caret(Movement::End, shift);
+        }
+        if pressed(Key::Backspace) {
+            self.delete(left);
+        }
+        if pressed(Key::Delete) {
+            self.delete(right);
+        }
+
+        let mut clipboard = None;
+        if command {
+            if pressed(Key::A) {
+                self.select_all();
+            }
+            if pressed(Key::C) {
+                clipboard = self.copy().map(str::to_owned);
+            }
+            if pressed(Key::X) {
+                clipboard = self.cut();
+            }
+            if pressed(Key::V) && let Some(text) = rl.get_clipboard_text() {
+                self.paste(&text);
+            }
+            if pressed(Key::Z) {
+                if shift { self.redo() } else { self.undo() };
+            }
+            if pressed(Key::Y) {
+                self.redo();
+            }
+        }
+
+        let mut buf = [0; 4];
+        while let Some(c) = rl.get_char_pressed() {
+            self.insert(c.encode_utf8(&mut buf));
+        }
+
+        if let Some(text) = clipboard {
+            // Control characters are never accepted, so the text has no nul
+            rl.set_clipboard_text(text);
+        }
+    }
+}
+
+/// Whether `c` continues the grapheme cluster before it
+///
+/// This covers combining marks, variation selectors, emoji modifiers and tags, which is enough to keep
+/// accented letters and emoji sequences together; it is not a full implementation of UAX #29.
+fn is_extend(c: char) -> bool {
+    matches!(c as u32,
+        0x0300..=0x036F // Combining diacritical marks
+        | 0x0483..=0x0489 // Cyrillic
+        | 0x0591..=0x05BD | 0x05BF | 0x05C1..=0x05C2 | 0x05C4..=0x05C5 | 0x05C7 // Hebrew
+        | 0x0610..=0x061A | 0x064B..=0x065F | 0x0670 | 0x06D6..=0x06DC | 0x06DF..=0x06E4 // Arabic
+        | 0x0900..=0x0903 | 0x093A..=0x094F | 0x0951..=0x0957 | 0x0962..=0x0963 // Devanagari
+        | 0x0E31 | 0x0E34..=0x0E3A | 0x0E47..=0x0E4E // Thai
+        | 0x1AB0..=0x1AFF | 0x1DC0..=0x1DFF // Combining diacritical marks extended and supplement
+        | 0x200C..=0x200D // Zero width non-joiner and joiner
+        | 0x20D0..=0x20FF // Combining marks for symbols
+        | 0x3099..=0x309A // Kana voicing marks
+        | 0xFE00..=0xFE0F // Variation selectors
+        | 0xFE20..=0xFE2F // Combining half marks
+        | 0x1F3FB..=0x1F3FF // Emoji skin tone modifiers
+        | 0xE0020..=0xE007F // Tags
+        | 0xE0100..=0xE01EF // Variation selectors supplement
+    )
+}
+
+fn is_regional_indicator(c: char) -> bool {
+    matches!(c as u32, 0x1F1E6..=0x1F1FF)
+}
+
+/// Byte offset of the end of the grapheme cluster starting at `start`
+fn cluster_end(text: &str, start: usize) -> usize {
+    let mut chars = text[start..].char_indices().peekable();
+    let Some((_, first)) = chars.next() else {
+        return start;
+    };
+    let mut end = start + first.len_utf8();
+    let mut previous = first;
+    let mut regional_indicators = usize::from(is_regional_indicator(first));
+    while let Some(&(offset, c)) = chars.peek() {
+        let joined = previous == '\u{200D}';
+        // Flags are pairs of regional indicators
+        let flag = regional_indicators == 1 && is_regional_indicator(c);
+        if !(joined || flag || is_extend(c)) {
+            break;
+        }
+        regional_indicators += usize::from(is_regional_indicator(c));
+        end = start + offset + c.len_utf8();
+        previous = c;
+        chars.next();
+    }
+    end
+}
+
+/// Byte offsets of every grapheme cluster boundary, including `0` and the end of the text
+fn boundaries(text: &str) -> impl Iterator<Item = usize> + '_ {
+    std::iter::successors(Some(0), move |&boundary| {
+        (boundary < text.len()).then(|| cluster_end(text, boundary))
+    })
+}
+
+fn is_boundary(text: &str, offset: usize) -> bool {
+    boundaries(text).any(|boundary| boundary == offset)
+}
+
+fn prev_boundary(text: &str, offset: usize) -> usize {
+    boundaries(text)
+        .take_while(|&boundary| boundary < offset)
+        .last()
+        .unwrap_or(0)
+}
+
+fn next_boundary(text: &str, offset: usize) -> usize {
+    boundaries(text)
+        .find(|&boundary| boundary > offset)
+        .unwrap_or(text.len())
+}
+
+/// Characters that make up words, as opposed to whitespace and punctuation
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CharClass {
+    Space,
+    Word,
+    Punctuation,
+}
+
+impl CharClass {
+    fn of(c: char) -> Self {
+        if c.is_whitespace() {
+            Self::Space
+        } else if c.is_alphanumeric() || c == '_' || is_extend(c) {
+            Self::Word
+        } else {
+            Self::Punctuation
+        }
+    }
+}
+
+/// Start of the word before `offset`, skipping whitespace
+fn word_left(text: &str, offset: usize) -> usize {
+    let mut chars = text[..offset].char_indices().rev().skip_while(|&(_, c)| c.is_whitespace()).peekable();
+    let Some(&(_, first)) = chars.peek() else {
+        return 0;
+    };
+    let class = CharClass::of(first);
+    chars.take_while(|&(_, c)| CharClass::of(c) == class)
+        .last()
+        .map_or(0, |(start, _)| start)
+}
+
+/// End of the word after `offset`, skipping whitespace
+fn word_right(text: &str, offset: usize) -> usize {
+    let mut chars = text[offset..].char_indices().skip_while(|&(_, c)| c.is_whitespace()).peekable();
+    let Some(&(_, first)) = chars.peek() else {
+        return text.len();
+    };
+    let class = CharClass::of(first);
+    chars.find(|&(_, c)| CharClass::of(c) != class)
+        .map_or(text.len(), |(end, _)| offset + end)
+}
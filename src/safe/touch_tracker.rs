@@ -0,0 +1,186 @@
+//! Fingers followed across frames
+//!
+//! Raylib reports touch points by slot, and a finger's slot changes when another finger lifts.
+//! [`TouchTracker`] follows each finger by its touch point id instead:
+//!
+//! ```ignore
+//! let mut touches = TouchTracker::new();
+//! while !rl.should_close() {
+//!     touches.update(&rl, rl.get_frame_duration());
+//!     for touch in touches.touches() {
+//!         match touch.phase {
+//!             TouchPhase::Began => ui.press(touch.id, touch.position),
+//!             TouchPhase::Moved => ui.drag(touch.id, touch.delta),
+//!             TouchPhase::Ended => ui.release(touch.id, touch.velocity),
+//!             TouchPhase::Stationary => {}
+//!         }
+//!     }
+//!     // ...
+//! }
+//! ```
+
+use super::*;
+
+/// Where a [`Touch`] is in its lifecycle
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TouchPhase {
+    /// The finger touched the screen this frame
+    Began,
+    /// The finger moved this frame
+    Moved,
+    /// The finger is down but did not move this frame
+    Stationary,
+    /// The finger was lifted this frame; the touch is dropped on the next update
+    Ended,
+}
+
+/// One finger on the screen, see [`TouchTracker`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Touch {
+    /// Touch point id, unique among the fingers down
+    pub id: u32,
+    /// Lifecycle phase as of the last update
+    pub phase: TouchPhase,
+    /// Current position, or the last one once ended
+    pub position: Vector2,
+    /// Where the finger touched the screen
+    pub start_position: Vector2,
+    /// Movement this frame
+    pub delta: Vector2,
+    /// Movement per second over the last frame it moved in; kept when the touch ends, for flicks
+    pub velocity: Vector2,
+    /// Time since the finger touched the screen
+    pub duration: Duration,
+}
+
+impl Touch {
+    /// Movement since the finger touched the screen
+    #[inline]
+    pub fn total_delta(&self) -> Vector2 {
+        Vector2 {
+            x: self.position.x - self.start_position.x,
+            y: self.position.y - self.start_position.y,
+        }
+    }
+
+    /// Check if the finger is still down
+    #[inline]
+    pub fn is_active(&self) -> bool {
+        self.phase != TouchPhase::Ended
+    }
+}
+
+/// Fingers on the screen, each keyed by its touch point id
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct TouchTracker {
+    touches: Vec<Touch>,
+}
+
+impl TouchTracker {
+    /// No fingers down
+    #[inline]
+    pub const fn new() -> Self {
+        Self { touches: Vec::new() }
+    }
+
+    /// Read the touch points of this frame; call once per frame
+    pub fn update(&mut self, rl: &WindowInner<'_>, frame_time: Duration) {
+        let points = (0..rl.get_touch_point_count())
+            .filter_map(|index| Some(TouchPoint {
+                id: rl.get_touch_point_id(index)?,
+                position: rl.get_touch_position(index),
+            }));
+        self.update_points(points, frame_time);
+    }
+
+    /// Update from the touch points of an input snapshot
+    #[inline]
+    pub fn update_from(&mut self, input: &InputState, frame_time: Duration) {
+        self.update_points(input.touches.iter().copied(), frame_time);
+    }
+
+    /// Update from the touch points down this frame, in any order
+    ///
+    /// Fingers not among `points` end; points with an id already seen this frame are ignored.
+    pub fn update_points(&mut self, points: impl IntoIterator<Item = TouchPoint>, frame_time: Duration) {
+        self.touches.retain(Touch::is_active);
+        let mut seen = vec![false; self.touches.len()];
+        let seconds = frame_time.as_secs_f32();
+        for point in points {
+            match self.touches.iter().position(|touch| touch.id == point.id) {
+                Some(index) if index < seen.len() => {
+                    if std::mem::replace(&mut seen[index], true) {
+                        continue;
+                    }
+                    let touch = &mut self.touches[index];
+                    touch.delta = Vector2 {
+                        x: point.position.x - touch.position.x,
+                        y: point.position.y - touch.position.y,
+                    };
+                    touch.position = point.position;
+                    touch.duration += frame_time;
+                    if touch.delta == Vector2::default() {
+                        touch.phase = TouchPhase::Stationary;
+                    } else {
+                        touch.phase = TouchPhase::Moved;
+                        if seconds > 0.0 {
+                            touch.velocity = Vector2 { x: touch.delta.x / seconds, y: touch.delta.y / seconds };
+                        }
+                    }
+                }
+                // Began earlier this frame
+                Some(_) => {}
+                None => self.touches.push(Touch {
+                    id: point.id,
+                    phase: TouchPhase::Began,
+                    position: point.position,
+                    start_position: point.position,
+                    delta: Vector2::default(),
+                    velocity: Vector2::default(),
+                    duration: Duration::ZERO,
+                }),
+            }
+        }
+        for (touch, _) in self.touches.iter_mut().zip(seen).filter(|(_, seen)| !seen) {
+            touch.phase = TouchPhase::Ended;
+            touch.delta = Vector2::default();
+            touch.duration += frame_time;
+        }
+    }
+
+    /// Every touch as of the last update, including those that ended in it; oldest first
+    #[inline]
+    pub fn touches(&self) -> &[Touch] {
+        &self.touches
+    }
+
+    /// Get a touch by id
+    #[inline]
+    pub fn get(&self, id: u32) -> Option<&Touch> {
+        self.touches.iter().find(|touch| touch.id == id)
+    }
+
+    /// Touches that began in the last update
+    #[inline]
+    pub fn began(&self) -> impl Iterator<Item = &Touch> {
+        self.touches.iter().filter(|touch| touch.phase == TouchPhase::Began)
+    }
+
+    /// Touches that ended in the last update
+    #[inline]
+    pub fn ended(&self) -> impl Iterator<Item = &Touch> {
+        self.touches.iter().filter(|touch| touch.phase == TouchPhase::Ended)
+    }
+
+    /// Fingers still down
+    #[inline]
+    pub fn active(&self) -> impl Iterator<Item = &Touch> {
+        self.touches.iter().filter(|touch| touch.is_active())
+    }
+
+    /// Number of fingers still down
+    #[inline]
+    pub fn active_count(&self) -> usize {
+        self.active().count()
+    }
+}
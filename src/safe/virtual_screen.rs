@@ -0,0 +1,180 @@
+//! Rendering at a fixed virtual resolution and scaling it to the window
+//!
+//! ```ignore
+//! let mut screen = VirtualScreen::new(&rl, 320, 180, ScaleMode::Integer).unwrap();
+//! while !rl.should_close() {
+//!     screen.draw(&mut rl, |rl, d, _m| {
+//!         d.clear_background(Color::BLACK);
+//!         let mouse = rl.get_mouse_position(); // in virtual pixels
+//!         // ...
+//!     });
+//!     rl.draw(|_rl, d, _m| {
+//!         screen.present(d);
+//!     });
+//! }
+//! ```
+
+use super::*;
+
+/// How a [`VirtualScreen`] is scaled to the window
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum ScaleMode {
+    /// Largest whole-number scale that fits, keeping pixels square and sharp
+    #[default]
+    Integer,
+    /// Largest scale that fits, keeping the aspect ratio
+    Fit,
+    /// Fill the whole window, ignoring the aspect ratio
+    Stretch,
+}
+
+impl ScaleMode {
+    /// Area of a `window_width` by `window_height` window covered by a `width` by `height` virtual screen
+    ///
+    /// The position is rounded down to whole pixels, because the mouse offset is set in whole pixels.
+    /// With [`ScaleMode::Integer`], a window smaller than the virtual screen shows it unscaled and cropped.
+    pub fn viewport(self, width: u32, height: u32, window_width: u32, window_height: u32) -> Rectangle {
+        let (width, height) = (width.max(1) as f32, height.max(1) as f32);
+        let (window_width, window_height) = (window_width as f32, window_height as f32);
+        let scale = match self {
+            Self::Stretch => return Rectangle::new(0.0, 0.0, window_width, window_height),
+            Self::Fit => (window_width / width).min(window_height / height),
+            Self::Integer => (window_width / width).min(window_height / height).floor().max(1.0),
+        };
+        let (scaled_width, scaled_height) = (width * scale, height * scale);
+        Rectangle::new(
+            ((window_width - scaled_width) / 2.0).floor(),
+            ((window_height - scaled_height) / 2.0).floor(),
+            scaled_width,
+            scaled_height,
+        )
+    }
+}
+
+/// A render texture drawn to the window at a fixed resolution, with letterboxing
+///
+/// [`VirtualScreen::draw()`] also maps the mouse into virtual pixels, so [`WindowInner::get_mouse_position()`]
+/// returns coordinates on the virtual screen until the `VirtualScreen` is dropped.
+pub struct VirtualScreen<'ctx> {
+    target: RenderTexture<'ctx>,
+    mode: ScaleMode,
+    letterbox: Color,
+    viewport: Rectangle,
+}
+
+impl Drop for VirtualScreen<'_> {
+    /// Reset the mouse mapping
+    #[inline]
+    fn drop(&mut self) {
+        // SAFETY: The render texture borrows the `WindowContext`, proving the window is still open
+        unsafe {
+            low::set_mouse_offset(0, 0);
+            low::set_mouse_scale(1.0, 1.0);
+        }
+    }
+}
+
+impl<'ctx> VirtualScreen<'ctx> {
+    /// Create a `width` by `height` virtual screen, [`None`] if the render texture could not be loaded
+    ///
+    /// The texture uses [`TextureFilter::Point`] so pixels stay sharp; see [`VirtualScreen::set_filter()`].
+    pub fn new(window: &WindowInner<'ctx>, width: u32, height: u32, mode: ScaleMode) -> Option<Self> {
+        let mut target = window.load_render_texture(width, height)?;
+        target.set_filter(TextureFilter::Point);
+        Some(Self {
+            viewport: mode.viewport(width, height, window.get_screen_width(), window.get_screen_height()),
+            target,
+            mode,
+            letterbox: Color::BLACK,
+        })
+    }
+
+    /// Virtual screen width
+    #[inline]
+    pub const fn width(&self) -> u32 {
+        self.target.width()
+    }
+
+    /// Virtual screen height
+    #[inline]
+    pub const fn height(&self) -> u32 {
+        self.target.height()
+    }
+
+    /// How the screen is scaled to the window
+    #[inline]
+    pub const fn mode(&self) -> ScaleMode {
+        self.mode
+    }
+
+    /// Change how the screen is scaled, taking effect on the next [`VirtualScreen::draw()`]
+    #[inline]
+    pub fn set_mode(&mut self, mode: ScaleMode) {
+        self.mode = mode;
+    }
+
+    /// Color of the bars around the scaled screen
+    #[inline]
+    pub const fn letterbox_color(&self) -> Color {
+        self.letterbox
+    }
+
+    /// Set the color of the bars around the scaled screen
+    #[inline]
+    pub fn set_letterbox_color(&mut self, color: Color) {
+        self.letterbox = color;
+    }
+
+    /// Set the filter used when scaling, [`TextureFilter::Point`] by default
+    #[inline]
+    pub fn set_filter(&mut self, filter: TextureFilter) {
+        self.target.set_filter(filter);
+    }
+
+    /// Area of the window the screen was last drawn to, in window pixels
+    #[inline]
+    pub const fn viewport(&self) -> Rectangle {
+        self.viewport
+    }
+
+    /// Convert a point in window pixels to virtual pixels
+    #[inline]
+    pub fn to_virtual(&self, point: Vector2) -> Vector2 {
+        Vector2 {
+            x: (point.x - self.viewport.x) * self.width() as f32 / self.viewport.width,
+            y: (point.y - self.viewport.y) * self.height() as f32 / self.viewport.height,
+        }
+    }
+
+    /// Convert a point in virtual pixels to window pixels
+    #[inline]
+    pub fn to_window(&self, point: Vector2) -> Vector2 {
+        Vector2 {
+            x: point.x * self.viewport.width / self.width() as f32 + self.viewport.x,
+            y: point.y * self.viewport.height / self.height() as f32 + self.viewport.y,
+        }
+    }
+
+    /// Draw to the virtual screen
+    ///
+    /// Updates the viewport for the current window size and maps the mouse into virtual pixels first.
+    pub fn draw<'w>(&mut self, window: &'w mut Window<'ctx>, f: impl for<'d> FnOnce(&'w mut WindowInner<'ctx>, &'d mut TextureMode, &'d mut BaseDrawMode)) {
+        self.viewport = self.mode.viewport(self.width(), self.height(), window.get_screen_width(), window.get_screen_height());
+        // A minimized window has no area to map from
+        if self.viewport.width > 0.0 && self.viewport.height > 0.0 {
+            window.set_mouse_offset(-self.viewport.x as i32, -self.viewport.y as i32);
+            window.set_mouse_scale(self.width() as f32 / self.viewport.width, self.height() as f32 / self.viewport.height);
+        }
+        window.texture_mode(&mut self.target, f);
+    }
+
+    /// Clear the window with the letterbox color and draw the scaled screen into the viewport
+    ///
+    /// Call inside [`Window::draw()`]; anything drawn afterwards appears on top, in window pixels.
+    pub fn present(&self, d: &mut impl Draw) {
+        d.clear_background(self.letterbox);
+        // Render textures are stored upside down
+        let source = Rectangle::new(0.0, 0.0, self.width() as f32, -(self.height() as f32));
+        d.draw_texture_pro(self.target.texture(), source, self.viewport, Vector2 { x: 0.0, y: 0.0 }, 0.0, Color::WHITE);
+    }
+}
@@ -0,0 +1,362 @@
+//! Audio sample data in CPU memory
+//!
+//! Loading, editing and exporting waves does not require an [`AudioDevice`].
+
+use std::{ops::{Bound, RangeBounds}, time::Duration};
+use super::*;
+use into_cstr::IntoCStrNulError;
+
+/// An error from loading or exporting a [`Wave`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WaveError {
+    /// The file name or file type contained a nul byte
+    InvalidPath(IntoCStrNulError),
+    /// Raylib could not load the data (missing file, unsupported format or corrupt data)
+    LoadFailed,
+    /// Raylib could not write the file (unsupported extension or I/O error)
+    ExportFailed,
+}
+
+impl From<IntoCStrNulError> for WaveError {
+    #[inline]
+    fn from(value: IntoCStrNulError) -> Self {
+        Self::InvalidPath(value)
+    }
+}
+
+impl std::fmt::Display for WaveError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::InvalidPath(e) => write!(f, "invalid path: {e}"),
+            Self::LoadFailed => f.write_str("failed to load wave data"),
+            Self::ExportFailed => f.write_str("failed to export wave data"),
+        }
+    }
+}
+
+impl std::error::Error for WaveError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::InvalidPath(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+/// Wave, audio wave data
+///
+/// A valid `Wave` always has at least one frame, 1 or more channels and a sample size of 8, 16 or 32 bits.
+/// Editing methods convert to 32-bit float internally and back to the wave's sample size.
+pub struct Wave {
+    // Private so the data can't be replaced with something of the wrong length
+    wave: sys::Wave,
+}
+
+impl Drop for Wave {
+    /// Unload wave data
+    #[inline]
+    fn drop(&mut self) {
+        // SAFETY: `self.wave.data` was allocated by Raylib and is owned by this `Wave`
+        unsafe {
+            low::unload_wave(self.wave);
+        }
+    }
+}
+
+impl Clone for Wave {
+    /// Copy a wave to a new wave
+    ///
+    /// # Panics
+    ///
+    /// If Raylib fails to allocate the copy.
+    #[inline]
+    fn clone(&self) -> Self {
+        // SAFETY: `self.wave` is valid
+        let wave = unsafe { low::wave_copy(self.wave) };
+        assert!(!wave.data.is_null(), "wave copy should be allocated");
+        Self { wave }
+    }
+}
+
+impl std::fmt::Debug for Wave {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Wave")
+            .field("frame_count", &self.frame_count())
+            .field("sample_rate", &self.sample_rate())
+            .field("sample_size", &self.sample_size())
+            .field("channels", &self.channels())
+            .finish_non_exhaustive()
+    }
+}
+
+impl Wave {
+    /// Take ownership of a wave loaded by Raylib, if it is valid
+    ///
+    /// # Safety
+    ///
+    /// `wave.data` must have been allocated by Raylib and must not be owned by anything else.
+    unsafe fn from_sys(wave: sys::Wave) -> Option<Self> {
+        // SAFETY: `is_wave_valid` only reads the wave's fields
+        let is_valid = unsafe { low::is_wave_valid(wave) } && matches!(wave.sampleSize, 8 | 16 | 32);
+        if is_valid {
+            Some(Self { wave })
+        } else {
+            if !wave.data.is_null() {
+                // SAFETY: Upheld by caller
+                unsafe {
+                    low::unload_wave(wave);
+                }
+            }
+            None
+        }
+    }
+
+    /// Load wave data from file
+    pub fn load(file_name: impl IntoCStr) -> Result<Self, WaveError> {
+        let file_name = file_name.into_cstr()?;
+        // SAFETY: The returned wave is owned by nothing else
+        unsafe { Self::from_sys(low::load_wave(file_name.as_ref())) }
+            .ok_or(WaveError::LoadFailed)
+    }
+
+    /// Load wave from memory buffer, `file_type` refers to extension: i.e. ".wav"
+    ///
+    /// WARNING: File extension must be provided in lower-case
+    pub fn load_from_memory(file_type: impl IntoCStr, file_data: &[u8]) -> Result<Self, WaveError> {
+        let file_type = file_type.into_cstr()?;
+        // SAFETY: The returned wave is owned by nothing else
+        unsafe { Self::from_sys(low::load_wave_from_memory(file_type.as_ref(), file_data)) }
+            .ok_or(WaveError::LoadFailed)
+    }
+
+    /// Build a 32-bit float wave from interleaved samples
+    ///
+    /// # Panics
+    ///
+    /// - `samples` is empty or does not contain whole frames
+    /// - `sample_rate` or `channels` is 0
+    /// - Raylib fails to allocate the wave data
+    pub fn from_samples(samples: &[f32], sample_rate: u32, channels: u32) -> Self {
+        assert!(sample_rate > 0, "sample rate should be at least 1");
+        assert!(channels > 0, "wave should have at least one channel");
+        assert!(!samples.is_empty(), "wave should have at least one frame");
+        assert!(samples.len().is_multiple_of(channels as usize), "samples should contain whole frames");
+        // SAFETY: Raylib's allocator has no preconditions
+        let data = unsafe { low::mem_alloc(size_of_val(samples)) }
+            .expect("wave data should be allocated");
+        // SAFETY: `data` was just allocated with room for every sample
+        unsafe {
+            std::ptr::copy_nonoverlapping(samples.as_ptr(), data.as_ptr().cast::<f32>(), samples.len());
+        }
+        Self {
+            wave: sys::Wave {
+                frameCount: (samples.len() / channels as usize).try_into().unwrap(),
+                sampleRate: sample_rate,
+                sampleSize: 32,
+                channels,
+                data: data.as_ptr(),
+            },
+        }
+    }
+
+    /// Total number of frames (considering channels)
+    #[inline]
+    pub const fn frame_count(&self) -> u32 {
+        self.wave.frameCount
+    }
+
+    /// Frequency (samples per second)
+    #[inline]
+    pub const fn sample_rate(&self) -> u32 {
+        self.wave.sampleRate
+    }
+
+    /// Bit depth (bits per sample): 8, 16 or 32
+    #[inline]
+    pub const fn sample_size(&self) -> u32 {
+        self.wave.sampleSize
+    }
+
+    /// Number of channels (1-mono, 2-stereo, ...)
+    #[inline]
+    pub const fn channels(&self) -> u32 {
+        self.wave.channels
+    }
+
+    /// Length of the wave when played at its sample rate
+    #[inline]
+    pub fn duration(&self) -> Duration {
+        Duration::from_secs_f64(f64::from(self.frame_count()) / f64::from(self.sample_rate()))
+    }
+
+    /// Copy the samples as interleaved 32-bit floats, normalized to `-1.0..=1.0` for 8 and 16-bit waves
+    ///
+    /// # Panics
+    ///
+    /// If Raylib fails to allocate the samples.
+    pub fn samples(&self) -> Vec<f32> {
+        // SAFETY: `self.wave` is valid
+        let samples = unsafe { low::load_wave_samples(self.wave) }
+            .expect("wave samples should be allocated");
+        let copy = samples.to_vec();
+        // SAFETY: Loaded above
+        unsafe {
+            low::unload_wave_samples(samples);
+        }
+        copy
+    }
+
+    /// Replace the samples, keeping sample rate, channels and sample size
+    fn replace_samples(&mut self, samples: &[f32]) {
+        let sample_size = self.sample_size();
+        *self = Self::from_samples(samples, self.sample_rate(), self.channels());
+        self.set_sample_size(sample_size);
+    }
+
+    /// Convert wave data to desired format
+    ///
+    /// # Panics
+    ///
+    /// - `sample_rate` or `channels` is 0
+    /// - `sample_size` is not 8, 16 or 32
+    pub fn convert(&mut self, sample_rate: u32, sample_size: u32, channels: u32) {
+        assert!(sample_rate > 0, "sample rate should be at least 1");
+        assert!(matches!(sample_size, 8 | 16 | 32), "sample size should be 8, 16 or 32 bits");
+        assert!(channels > 0, "wave should have at least one channel");
+        // SAFETY: `self.wave` is valid and the new format is supported
+        unsafe {
+            low::wave_format(&mut self.wave, sample_rate, sample_size, channels);
+        }
+    }
+
+    /// Resample to a new sample rate, keeping the duration
+    ///
+    /// # Panics
+    ///
+    /// If `sample_rate` is 0.
+    #[inline]
+    pub fn resample(&mut self, sample_rate: u32) {
+        self.convert(sample_rate, self.sample_size(), self.channels());
+    }
+
+    /// Change the number of channels, mixing down or duplicating as needed
+    ///
+    /// # Panics
+    ///
+    /// If `channels` is 0.
+    #[inline]
+    pub fn set_channels(&mut self, channels: u32) {
+        self.convert(self.sample_rate(), self.sample_size(), channels);
+    }
+
+    /// Change the bit depth: 8, 16 or 32 (float)
+    ///
+    /// # Panics
+    ///
+    /// If `sample_size` is not 8, 16 or 32.
+    #[inline]
+    pub fn set_sample_size(&mut self, sample_size: u32) {
+        self.convert(self.sample_rate(), sample_size, self.channels());
+    }
+
+    /// Crop to a time range, clamped to the length of the wave
+    ///
+    /// # Panics
+    ///
+    /// If the range contains no frames.
+    pub fn crop(&mut self, range: impl RangeBounds<Duration>) {
+        let to_frame = |time: &Duration| {
+            ((time.as_secs_f64() * f64::from(self.sample_rate())).round() as u32).min(self.frame_count())
+        };
+        let start = match range.start_bound() {
+            Bound::Included(t) => to_frame(t),
+            Bound::Excluded(t) => to_frame(t) + 1,
+            Bound::Unbounded => 0,
+        };
+        let end = match range.end_bound() {
+            Bound::Included(t) => (to_frame(t) + 1).min(self.frame_count()),
+            Bound::Excluded(t) => to_frame(t),
+            Bound::Unbounded => self.frame_count(),
+        };
+        assert!(start < end, "cropped wave should have at least one frame");
+        // SAFETY: `self.wave` is valid and `start..end` is a non-empty range of its frames
+        unsafe {
+            low::wave_crop(&mut self.wave, start, end);
+        }
+    }
+
+    /// Scale so the loudest sample reaches full scale (1.0), does nothing for silent waves
+    pub fn normalize(&mut self) {
+        let mut samples = self.samples();
+        let peak = samples.iter().fold(0.0f32, |peak, s| peak.max(s.abs()));
+        if peak > 0.0 {
+            samples.iter_mut().for_each(|s| *s /= peak);
+            self.replace_samples(&samples);
+        }
+    }
+
+    /// Reverse the order of the frames
+    pub fn reverse(&mut self) {
+        let channels = self.channels() as usize;
+        let samples = self.samples();
+        let reversed: Vec<f32> = samples.chunks_exact(channels)
+            .rev()
+            .flatten()
+            .copied()
+            .collect();
+        self.replace_samples(&reversed);
+    }
+
+    /// Convert `other` to this wave's format
+    fn samples_in_format_of(&self, other: &Wave) -> Vec<f32> {
+        if (other.sample_rate(), other.channels()) == (self.sample_rate(), self.channels()) {
+            other.samples()
+        } else {
+            let mut other = other.clone();
+            other.convert(self.sample_rate(), 32, self.channels());
+            other.samples()
+        }
+    }
+
+    /// Append `other`, converted to this wave's format
+    pub fn concat(&mut self, other: &Wave) {
+        let mut samples = self.samples();
+        samples.extend(self.samples_in_format_of(other));
+        self.replace_samples(&samples);
+    }
+
+    /// Add `other`, converted to this wave's format, sample by sample
+    ///
+    /// The result is as long as the longer wave. Samples are not limited, so mixing loud waves can clip
+    /// when stored as 8 or 16-bit; use [`Wave::normalize()`] afterwards or lower the volume beforehand.
+    pub fn mix(&mut self, other: &Wave) {
+        let mut samples = self.samples();
+        let other = self.samples_in_format_of(other);
+        if other.len() > samples.len() {
+            samples.resize(other.len(), 0.0);
+        }
+        samples.iter_mut()
+            .zip(other)
+            .for_each(|(s, o)| *s += o);
+        self.replace_samples(&samples);
+    }
+
+    /// Export wave data to file, the format is chosen by extension (.wav, .qoa, .raw)
+    pub fn export(&self, file_name: impl IntoCStr) -> Result<(), WaveError> {
+        let file_name = file_name.into_cstr()?;
+        // SAFETY: `self.wave` is valid
+        if unsafe { low::export_wave(self.wave, file_name.as_ref()) } {
+            Ok(())
+        } else {
+            Err(WaveError::ExportFailed)
+        }
+    }
+
+    /// Export wave sample data to code (.h)
+    pub fn export_as_code(&self, file_name: impl IntoCStr) -> Result<(), WaveError> {
+        let file_name = file_name.into_cstr()?;
+        // SAFETY: `self.wave` is valid
+        unsafe { low::export_wave_as_code(self.wave, file_name.as_ref()) }
+            .map_err(|()| WaveError::ExportFailed)
+    }
+}
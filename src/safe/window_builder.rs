@@ -0,0 +1,181 @@
+//! Configuring a window before it opens
+
+use std::ffi::CString;
+use super::*;
+use crate::low::types::ConfigFlags;
+
+/// Settings for opening a window, see [`WindowContext::builder()`]
+///
+/// Each setting is applied at the point Raylib requires:
+/// config flags before the window is created, everything else right after.
+pub struct WindowBuilder {
+    width: u32,
+    height: u32,
+    title: CString,
+    flags: ConfigFlags,
+    min_size: Option<(u32, u32)>,
+    max_size: Option<(u32, u32)>,
+    monitor: Option<usize>,
+    icon: Option<Image>,
+    opacity: Option<f32>,
+    target_fps: Option<u32>,
+    exit_key: Option<Option<KeyboardKey>>,
+}
+
+/// Settings from a [`WindowBuilder`] that did not take effect on the opened window
+///
+/// Raylib can only report what its platform layer records. Hints such as
+/// [`ConfigFlags::VSYNC_HINT`] and [`ConfigFlags::MSAA_4X_HINT`] are passed on to the driver,
+/// which may still ignore them without Raylib noticing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IgnoredSettings {
+    /// Requested flags that are not set on the opened window
+    pub flags: ConfigFlags,
+    /// The requested monitor, if it is not connected; the window stays on the primary monitor
+    pub monitor: Option<usize>,
+}
+
+impl IgnoredSettings {
+    /// Whether every setting took effect
+    #[inline]
+    pub const fn is_empty(&self) -> bool {
+        self.flags.is_empty() && self.monitor.is_none()
+    }
+}
+
+impl WindowContext {
+    /// Configure a window before opening it
+    ///
+    /// # Panics
+    /// - [`IntoCStr::into_cstr()`] fails for `title`
+    #[inline]
+    pub fn builder(width: u32, height: u32, title: impl IntoCStr) -> WindowBuilder {
+        WindowBuilder {
+            width,
+            height,
+            title: title.into_cstr().unwrap().as_ref().to_owned(),
+            flags: ConfigFlags::empty(),
+            min_size: None,
+            max_size: None,
+            monitor: None,
+            icon: None,
+            opacity: None,
+            target_fps: None,
+            exit_key: None,
+        }
+    }
+}
+
+impl WindowBuilder {
+    /// Add configuration flags (MSAA, vsync, HiDPI, resizable, transparent, ...)
+    #[inline]
+    pub fn flags(mut self, flags: ConfigFlags) -> Self {
+        self.flags |= flags;
+        self
+    }
+
+    /// Set window minimum dimensions (for [`ConfigFlags::WINDOW_RESIZABLE`])
+    #[inline]
+    pub fn min_size(mut self, width: u32, height: u32) -> Self {
+        self.min_size = Some((width, height));
+        self
+    }
+
+    /// Set window maximum dimensions (for [`ConfigFlags::WINDOW_RESIZABLE`])
+    #[inline]
+    pub fn max_size(mut self, width: u32, height: u32) -> Self {
+        self.max_size = Some((width, height));
+        self
+    }
+
+    /// Open the window on a specific monitor
+    #[inline]
+    pub fn monitor(mut self, monitor: usize) -> Self {
+        self.monitor = Some(monitor);
+        self
+    }
+
+    /// Set icon for window (RGBA 32bit)
+    #[inline]
+    pub fn icon(mut self, icon: Image) -> Self {
+        self.icon = Some(icon);
+        self
+    }
+
+    /// Set window opacity [0.0..=1.0]
+    ///
+    /// # Panics
+    /// - `opacity` is outside of `0.0..=1.0`
+    #[inline]
+    pub fn opacity(mut self, opacity: f32) -> Self {
+        assert!((0.0..=1.0).contains(&opacity),
+            "opacity out of range\n expect: [0.0..=1.0]\n actual: {opacity}",
+        );
+        self.opacity = Some(opacity);
+        self
+    }
+
+    /// Set target FPS (maximum)
+    #[inline]
+    pub fn target_fps(mut self, fps: u32) -> Self {
+        self.target_fps = Some(fps);
+        self
+    }
+
+    /// Set a custom key to exit program (default is ESC), [`None`] disables the exit key
+    #[inline]
+    pub fn exit_key(mut self, key: Option<KeyboardKey>) -> Self {
+        self.exit_key = Some(key);
+        self
+    }
+
+    /// Initialize window and OpenGL context with these settings
+    ///
+    /// See [`WindowContext::init()`] for when this fails.
+    pub fn build(self) -> Result<(WindowContext, IgnoredSettings), WindowInitError> {
+        WindowContext::claim()?;
+        // SAFETY: The claim guarantees no `WindowContext` is open, so the flags only configure the window about to be created
+        unsafe {
+            low::set_config_flags(sys::ConfigFlags(self.flags.bits() as _));
+        }
+        // SAFETY: The claim was just taken
+        let mut context = unsafe { WindowContext::init_claimed(self.width, self.height, &self.title) }?;
+        let mut ignored = IgnoredSettings {
+            flags: self.flags
+                .iter()
+                // SAFETY: Existence of `WindowContext` proves window is initialized
+                .filter(|&flag| !unsafe { low::is_window_state(sys::ConfigFlags(flag.bits() as _)) })
+                .collect(),
+            monitor: None,
+        };
+        {
+            let mut window = context.window();
+            if let Some(monitor) = self.monitor {
+                if monitor < window.get_monitor_count() {
+                    window.set_window_monitor(monitor);
+                } else {
+                    ignored.monitor = Some(monitor);
+                }
+            }
+            if let Some((width, height)) = self.min_size {
+                window.set_window_min_size(width, height);
+            }
+            if let Some((width, height)) = self.max_size {
+                window.set_window_max_size(width, height);
+            }
+            if let Some(icon) = &self.icon {
+                window.set_window_icon(icon);
+            }
+            if let Some(opacity) = self.opacity {
+                window.set_window_opacity(opacity);
+            }
+            if let Some(fps) = self.target_fps {
+                window.set_target_fps(fps);
+            }
+            if let Some(key) = self.exit_key {
+                window.set_exit_key(key);
+            }
+        }
+        Ok((context, ignored))
+    }
+}
@@ -0,0 +1,194 @@
+//! Saving the window's placement and restoring it on the next run
+//!
+//! A [`WindowGeometry`] converts to and from a line-based text format:
+//!
+//! ```text
+//! monitor=1
+//! position=120,80
+//! size=1280,720
+//! maximized=false
+//! fullscreen=false
+//! ```
+//!
+//! Lines are `key=value` pairs in any order. Blank lines and lines starting with `#` are skipped,
+//! and unknown keys are ignored so that newer versions can add fields.
+
+use std::str::FromStr;
+use super::*;
+
+/// Window placement, see [`WindowInner::window_geometry()`] and [`WindowInner::restore_window_geometry()`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WindowGeometry {
+    /// Monitor the window was on
+    pub monitor: usize,
+    /// Horizontal offset of the window from the left edge of its monitor
+    pub x: i32,
+    /// Vertical offset of the window from the top edge of its monitor
+    pub y: i32,
+    /// Screen width
+    pub width: u32,
+    /// Screen height
+    pub height: u32,
+    /// Whether the window was maximized
+    pub maximized: bool,
+    /// Whether the window was fullscreen
+    pub fullscreen: bool,
+}
+
+impl WindowGeometry {
+    /// Position and size on `monitor`, as `(x, y, width, height)` on the virtual desktop
+    ///
+    /// The size is shrunk to the monitor's [work area](Monitor::work_area) and the position is
+    /// moved so that the window is fully on the monitor.
+    pub fn fit_to(&self, monitor: &Monitor, dpi_scale: Vector2) -> (i32, i32, u32, u32) {
+        let (_, _, max_width, max_height) = monitor.work_area(dpi_scale, 0);
+        let width = self.width.clamp(1, max_width);
+        let height = self.height.clamp(1, max_height);
+        let (x, y) = monitor.clamped(
+            monitor.x.saturating_add(self.x),
+            monitor.y.saturating_add(self.y),
+            width,
+            height,
+        );
+        (x, y, width, height)
+    }
+}
+
+impl std::fmt::Display for WindowGeometry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "monitor={}", self.monitor)?;
+        writeln!(f, "position={},{}", self.x, self.y)?;
+        writeln!(f, "size={},{}", self.width, self.height)?;
+        writeln!(f, "maximized={}", self.maximized)?;
+        writeln!(f, "fullscreen={}", self.fullscreen)
+    }
+}
+
+/// An error from parsing a [`WindowGeometry`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WindowGeometryParseError {
+    /// A line is not a `key=value` pair
+    MalformedLine {
+        /// 1-based line number
+        line: usize,
+    },
+    /// A known key has a value that could not be parsed
+    InvalidValue {
+        /// 1-based line number
+        line: usize,
+        /// The key whose value is invalid
+        key: &'static str,
+    },
+    /// A required key is absent
+    MissingKey(&'static str),
+}
+
+impl std::fmt::Display for WindowGeometryParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::MalformedLine { line } => write!(f, "line {line}: expected `key=value`"),
+            Self::InvalidValue { line, key } => write!(f, "line {line}: invalid value for `{key}`"),
+            Self::MissingKey(key) => write!(f, "missing `{key}`"),
+        }
+    }
+}
+
+impl std::error::Error for WindowGeometryParseError {}
+
+/// Parse `"a,b"`
+fn parse_pair<T: FromStr>(value: &str) -> Option<(T, T)> {
+    let (a, b) = value.split_once(',')?;
+    Some((a.trim().parse().ok()?, b.trim().parse().ok()?))
+}
+
+impl FromStr for WindowGeometry {
+    type Err = WindowGeometryParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut monitor = None;
+        let mut position = None;
+        let mut size = None;
+        let mut maximized = None;
+        let mut fullscreen = None;
+
+        for (index, line) in s.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let number = index + 1;
+            let (key, value) = line.split_once('=')
+                .ok_or(WindowGeometryParseError::MalformedLine { line: number })?;
+            let value = value.trim();
+            let invalid = |key| WindowGeometryParseError::InvalidValue { line: number, key };
+            match key.trim() {
+                "monitor" => monitor = Some(value.parse::<usize>().map_err(|_| invalid("monitor"))?),
+                "position" => position = Some(parse_pair::<i32>(value).ok_or_else(|| invalid("position"))?),
+                "size" => size = Some(parse_pair::<u32>(value).ok_or_else(|| invalid("size"))?),
+                "maximized" => maximized = Some(value.parse::<bool>().map_err(|_| invalid("maximized"))?),
+                "fullscreen" => fullscreen = Some(value.parse::<bool>().map_err(|_| invalid("fullscreen"))?),
+                _ => {}
+            }
+        }
+
+        let (x, y) = position.ok_or(WindowGeometryParseError::MissingKey("position"))?;
+        let (width, height) = size.ok_or(WindowGeometryParseError::MissingKey("size"))?;
+        Ok(Self {
+            monitor: monitor.ok_or(WindowGeometryParseError::MissingKey("monitor"))?,
+            x,
+            y,
+            width,
+            height,
+            maximized: maximized.unwrap_or(false),
+            fullscreen: fullscreen.unwrap_or(false),
+        })
+    }
+}
+
+impl WindowInner<'_> {
+    /// Take a snapshot of the window's placement
+    ///
+    /// Raylib does not report the size a maximized or fullscreen window returns to,
+    /// so the snapshot holds the current size; save it before maximizing to keep the windowed size.
+    pub fn window_geometry(&self) -> WindowGeometry {
+        let position = self.get_window_position();
+        let monitor = self.current_monitor();
+        let (origin_x, origin_y) = monitor.as_ref().map_or((0, 0), |monitor| (monitor.x, monitor.y));
+        WindowGeometry {
+            monitor: monitor.map_or(0, |monitor| monitor.index),
+            x: position.x as i32 - origin_x,
+            y: position.y as i32 - origin_y,
+            width: self.get_screen_width(),
+            height: self.get_screen_height(),
+            maximized: self.is_window_maximized(),
+            fullscreen: self.is_window_fullscreen(),
+        }
+    }
+
+    /// Move and resize the window to a saved placement
+    ///
+    /// If the saved monitor is no longer connected, the window is placed on the primary monitor instead.
+    /// Either way it is [fit](WindowGeometry::fit_to) to the monitor, so it can't end up off-screen.
+    ///
+    /// Returns the monitor the window was placed on, [`None`] if no monitor is connected.
+    pub fn restore_window_geometry(&mut self, geometry: &WindowGeometry) -> Option<usize> {
+        let monitor = self.monitor(geometry.monitor)
+            .or_else(|| self.monitor(0))?;
+        if self.is_window_fullscreen() {
+            self.toggle_fullscreen();
+        }
+        if self.is_window_maximized() {
+            self.restore_window();
+        }
+        let (x, y, width, height) = geometry.fit_to(&monitor, self.get_window_scale_dpi());
+        self.set_window_size(width, height);
+        self.set_window_position(x, y);
+        if geometry.fullscreen {
+            self.set_window_monitor(monitor.index);
+            self.toggle_fullscreen();
+        } else if geometry.maximized {
+            self.maximize_window();
+        }
+        Some(monitor.index)
+    }
+}
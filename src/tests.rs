@@ -3,7 +3,10 @@
 use std::sync::{Mutex, MutexGuard};
 use crate::prelude::*;
 
-/// Exclusive access to Raylib window
+/// Exclusive access to Raylib's global state
+///
+/// Opening a window on a thread while another test's window is open fails with
+/// [`WindowInitError::OpenOnOtherThread`], so tests that open one wait their turn.
 fn await_turn() -> MutexGuard<'static, ()> {
     static TALKING_STICK: Mutex<()> = Mutex::new(());
 
@@ -19,8 +22,9 @@ fn await_turn() -> MutexGuard<'static, ()> {
 fn test0() {
     let _turn = await_turn();
     {
-        let mut rl = Window::init(1280, 720, c"Test 0")
+        let mut ctx = WindowContext::init(1280, 720, c"Test 0")
             .unwrap();
+        let mut rl = ctx.window();
 
         while !rl.should_close() {
             rl.draw(|rl, d, m| {
@@ -49,6 +53,138 @@ fn test0() {
     }
 }
 
+#[test]
+fn test_window_single_instance() {
+    let _turn = await_turn();
+    let ctx = WindowContext::init(320, 240, c"Single instance")
+        .unwrap();
+    assert_eq!(WindowContext::init(320, 240, c"Second").err(), Some(WindowInitError::AlreadyOpen));
+
+    let owner = std::thread::current().id();
+    let from_other_thread = std::thread::spawn(|| WindowContext::init(320, 240, c"Other thread").err())
+        .join()
+        .unwrap();
+    assert_eq!(from_other_thread, Some(WindowInitError::OpenOnOtherThread(owner)));
+    // std::thread::spawn(move || drop(ctx)); // Uncommenting this line should result in E0277 error
+
+    drop(ctx);
+    let reopened = std::thread::spawn(|| WindowContext::init(320, 240, c"Reopened").map(drop))
+        .join()
+        .unwrap();
+    assert_eq!(reopened, Ok(()), "closing the window should release it for other threads");
+}
+
+#[test]
+fn test_gpu_resources_borrow_context() {
+    let _turn = await_turn();
+    let mut ctx = WindowContext::init(320, 240, c"GPU resources")
+        .unwrap();
+    let mut rl = ctx.window();
+    let mut target = rl.load_render_texture(64, 32)
+        .unwrap();
+    assert_eq!((target.width(), target.height()), (64, 32));
+
+    rl.texture_mode(&mut target, |_rl, d, _m| {
+        d.clear_background(Color::RED);
+    });
+    rl.draw(|_rl, d, _m| {
+        d.clear_background(Color::BLACK);
+        d.draw_texture(target.texture(), 0, 0, Color::WHITE);
+    });
+    // drop(ctx); // Uncommenting this line should result in E0505 error
+    drop(target);
+}
+
+#[test]
+fn test_shader_compile_failure() {
+    let _turn = await_turn();
+    let mut ctx = WindowContext::init(320, 240, c"Shader failure")
+        .unwrap();
+    let rl = ctx.window();
+    assert!(rl.load_shader_from_memory(None, Some("this is not glsl")).is_none(), "the default shader is not a failed shader");
+    assert!(rl.load_shader_from_memory(Some("void main() { garbage }"), None).is_none());
+    assert!(rl.load_shader(None::<&str>, Some("missing.fs")).is_none());
+}
+
+#[test]
+fn test_run_async_script() {
+    use std::{cell::Cell, rc::Rc};
+    let _turn = await_turn();
+    let mut ctx = WindowContext::init(320, 240, c"Async script")
+        .unwrap();
+    let mut rl = ctx.window();
+    let spawned_done = Rc::new(Cell::new(false));
+    let done = spawned_done.clone();
+    let last_frame = rl.run_async(
+        |frames| async move {
+            let task_frames = frames.clone();
+            frames.spawn(async move {
+                task_frames.next_frame().await;
+                done.set(true);
+            });
+            frames.wait_frames(3).await;
+            frames.frame()
+        },
+        |_rl, d, _m| {
+            d.clear_background(Color::BLACK);
+        },
+    );
+    assert_eq!(last_frame, Some(4), "the script should start in frame 1 and finish 3 frames later");
+    assert!(spawned_done.get());
+}
+
+#[test]
+fn test_input_recorder_exclusive() {
+    let _turn = await_turn();
+    let mut ctx = WindowContext::init(320, 240, c"Input recorder")
+        .unwrap();
+    let mut rl = ctx.window();
+    let recorder = InputRecorder::start(&mut rl).unwrap();
+    assert!(InputRecorder::start(&mut rl).is_none(), "only one recorder should exist at a time");
+    for _ in 0..3 {
+        rl.draw(|_rl, d, _m| d.clear_background(Color::BLACK));
+    }
+    assert!(!recorder.is_full());
+    drop(recorder.stop());
+    let recorder = InputRecorder::start(&mut rl);
+    assert!(recorder.is_some(), "stopping should allow a new recorder");
+}
+
+#[cfg(feature = "custom_frame_control")]
+#[test]
+fn test_custom_frame_control() {
+    use std::time::Duration;
+    let _turn = await_turn();
+    let mut ctx = WindowContext::init(320, 240, c"Custom frame control")
+        .unwrap();
+    let mut rl = ctx.window();
+    rl.set_target_fps(0);
+    for _ in 0..2 {
+        let frame = rl.render(|_rl, d, _m| d.clear_background(Color::RED));
+        rl.present(frame);
+    }
+    let presented = rl.get_frame_duration();
+
+    std::thread::sleep(Duration::from_millis(20));
+    drop(rl.render(|_rl, d, _m| d.clear_background(Color::BLUE)));
+    assert_eq!(rl.get_frame_duration(), presented, "a dropped frame should not be shown or timed");
+    for _ in 0..3 {
+        rl.poll_input_events();
+        assert!(!rl.is_key_pressed(KeyboardKey::Space));
+    }
+    assert_eq!(rl.get_frame_duration(), presented, "polling should not end a frame");
+    let frame = rl.render(|_rl, d, _m| d.clear_background(Color::GREEN));
+    rl.present(frame);
+    assert!(rl.get_frame_duration() >= Duration::from_millis(20), "the skipped frame should count towards the next");
+
+    rl.set_target_fps(20);
+    for _ in 0..2 {
+        rl.draw(|_rl, d, _m| d.clear_background(Color::BLACK));
+    }
+    assert!(rl.get_frame_duration() >= Duration::from_millis(45), "drawing should wait for the target FPS");
+    assert!(rl.get_fps() > 0, "frames were measured, so FPS should be known");
+}
+
 #[test]
 fn test1() {
     use crate::low::text::TextToSnakeHandle;
@@ -58,3 +194,591 @@ fn test1() {
     assert_eq!(unsafe { handle.text_to_snake(c"AppleOrangeBanana") }, c"apple_orange_banana");
     // assert_eq!(result, c"hello_world"); // Uncommenting this line should result in E0499 error
 }
+
+#[cfg(feature = "null_audio")]
+#[test]
+fn test_null_audio_capture() {
+    let _turn = await_turn();
+    let audio = AudioDevice::init()
+        .unwrap();
+    assert!(AudioDevice::init().is_none(), "only one audio device may exist at a time");
+
+    let mut capture = audio.capture_mixed_output()
+        .unwrap();
+    assert!(audio.capture_mixed_output().is_none(), "only one capture may exist at a time");
+    std::thread::sleep(std::time::Duration::from_millis(100));
+    let samples = capture.take();
+    assert!(!samples.is_empty(), "null backend should consume samples in real time");
+    assert!(samples.iter().all(|&s| s == 0.0), "nothing is playing, so the mix should be silent");
+}
+
+#[test]
+fn test_ring_buffer_wraparound() {
+    let (mut producer, mut consumer) = ring_buffer::<u32>(3);
+    assert_eq!(producer.capacity(), 4);
+    let mut out = [0; 4];
+    for round in 0..10 {
+        let base = round * 3;
+        assert_eq!(producer.push_slice(&[base, base + 1, base + 2]), 3);
+        assert_eq!(consumer.pop_slice(&mut out), 3);
+        assert_eq!(out[..3], [base, base + 1, base + 2]);
+    }
+    assert_eq!(producer.push_slice(&[1, 2, 3, 4, 5, 6]), 4);
+    assert_eq!(consumer.overruns(), 2);
+    assert_eq!(consumer.pop(), Some(1));
+    assert_eq!(consumer.skip(10), 3);
+    assert!(consumer.is_empty());
+}
+
+#[test]
+fn test_audio_analyzer() {
+    let config = AnalyzerConfig {
+        fft_size: 1024,
+        sample_rate: 48000,
+        ..AnalyzerConfig::default()
+    };
+    let mut analyzer = AudioAnalyzer::new(config);
+
+    analyzer.push_frames(&[[0.0; 2]; 4096]);
+    assert!(!analyzer.is_onset(), "silence has no onsets");
+    assert_eq!(analyzer.levels(), AudioLevels::default());
+
+    // A sine centered on bin 32
+    analyzer.update();
+    let frequency = analyzer.bin_frequency(32);
+    let sine: Vec<[f32; 2]> = (0..4096)
+        .map(|i| (2.0 * std::f32::consts::PI * frequency * i as f32 / 48000.0).sin() * 0.5)
+        .map(|s| [s, s])
+        .collect();
+    analyzer.push_frames(&sine);
+    assert_eq!(analyzer.onsets(), 1, "the sine starting should be one onset");
+
+    let spectrum = analyzer.spectrum();
+    let (peak_bin, &magnitude) = spectrum.iter()
+        .enumerate()
+        .max_by(|a, b| a.1.total_cmp(b.1))
+        .unwrap();
+    assert_eq!(peak_bin, 32);
+    assert!((magnitude - 0.5).abs() < 0.01, "magnitude was {magnitude}");
+
+    let levels = analyzer.levels();
+    assert!((levels.peak - 0.5).abs() < 0.01, "peak was {}", levels.peak);
+    assert!((levels.rms - 0.5 / 2f32.sqrt()).abs() < 0.01, "rms was {}", levels.rms);
+
+    analyzer.update();
+    analyzer.push_frames(&sine);
+    assert!(!analyzer.is_onset(), "a steady sine has no onsets");
+}
+
+#[cfg(feature = "null_audio")]
+#[test]
+fn test_queued_audio_stream() {
+    use std::time::{Duration, Instant};
+    let _turn = await_turn();
+    let audio = AudioDevice::init()
+        .unwrap();
+    let (mut stream, mut producer) = QueuedAudioStream::load(&audio, 48000, 2, Duration::from_millis(100))
+        .unwrap();
+    let mut capture = audio.capture_mixed_output()
+        .unwrap();
+
+    let decoder = std::thread::spawn(move || {
+        let mut phase = 0.0f32;
+        let mut pushed = 0;
+        let mut chunk = Vec::new();
+        while pushed < 24000 {
+            chunk.clear();
+            for _ in 0..480 {
+                let s = phase.sin() * 0.5;
+                chunk.extend([s, s]);
+                phase += 2.0 * std::f32::consts::PI * 440.0 / 48000.0;
+            }
+            while producer.free_frames() < 480 {
+                std::thread::sleep(Duration::from_millis(1));
+            }
+            pushed += producer.push(&chunk);
+        }
+        producer.overrun_frames()
+    });
+
+    stream.feed();
+    stream.play();
+    let deadline = Instant::now() + Duration::from_secs(5);
+    while !decoder.is_finished() {
+        assert!(Instant::now() < deadline, "the stream should drain the decoder's frames");
+        stream.feed();
+        std::thread::sleep(Duration::from_millis(5));
+    }
+    assert_eq!(decoder.join().unwrap(), 0, "the decoder waited for free space, so nothing was dropped");
+    assert!(capture.take().iter().any(|&s| s.abs() > 0.1), "the sine should reach the mix");
+}
+
+#[test]
+fn test_wave_round_trip() {
+    use std::time::Duration;
+    let sine: Vec<f32> = (0..22050)
+        .map(|i| (2.0 * std::f32::consts::PI * 441.0 * i as f32 / 22050.0).sin() * 0.25)
+        .collect();
+    let mut wave = Wave::from_samples(&sine, 22050, 1);
+    assert_eq!(wave.duration(), Duration::from_secs(1));
+
+    wave.normalize();
+    assert!(wave.samples().iter().any(|s| s.abs() == 1.0), "normalized wave should reach full scale");
+
+    wave.crop(..Duration::from_millis(500));
+    assert_eq!(wave.frame_count(), 11025);
+
+    let mut tail = wave.clone();
+    tail.reverse();
+    assert_eq!(tail.samples().last(), wave.samples().first());
+    wave.concat(&tail);
+    assert_eq!(wave.frame_count(), 22050);
+
+    wave.set_sample_size(16);
+    wave.set_channels(2);
+    let path = std::env::temp_dir().join("raylib_amy_wave_round_trip.wav");
+    wave.export(&*path).unwrap();
+    let loaded = Wave::load(&*path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    assert_eq!((loaded.sample_rate(), loaded.sample_size(), loaded.channels()), (22050, 16, 2));
+    assert_eq!(loaded.frame_count(), wave.frame_count());
+    assert!(wave.samples().iter().zip(loaded.samples()).all(|(a, b)| (a - b).abs() <= 1.0 / 32768.0));
+
+    assert_eq!(wave.export(c"no_such_dir/wave.wav"), Err(WaveError::ExportFailed));
+    assert_eq!(Wave::load(c"no_such_file.wav").unwrap_err(), WaveError::LoadFailed);
+}
+
+#[test]
+fn test_monitor_placement() {
+    let monitor = Monitor {
+        index: 1,
+        name: String::from("Secondary"),
+        x: 1920,
+        y: -200,
+        width: 2560,
+        height: 1440,
+        physical_width: 597,
+        physical_height: 336,
+        refresh_rate: 144,
+    };
+    assert!(monitor.contains(1920, -200));
+    assert!(!monitor.contains(1919, 0));
+    assert!(!monitor.contains(1920 + 2560, 0));
+    assert_eq!(monitor.centered(1280, 720), (1920 + 640, -200 + 360));
+    assert_eq!(monitor.centered(3000, 1440), (1920 - 220, -200));
+    assert_eq!(monitor.clamped(5000, -1000, 800, 600), (1920 + 2560 - 800, -200));
+    assert_eq!(monitor.clamped(0, 0, 3000, 2000), (1920, -200));
+    assert_eq!(monitor.work_area(Vector2 { x: 2.0, y: 2.0 }, 10), (1930, -190, 1260, 700));
+    assert_eq!(monitor.work_area(Vector2 { x: 0.0, y: 1.0 }, 0), (1920, -200, 2560, 1440));
+}
+
+#[test]
+fn test_window_geometry_round_trip() {
+    let geometry = WindowGeometry {
+        monitor: 1,
+        x: -40,
+        y: 100,
+        width: 1280,
+        height: 720,
+        maximized: true,
+        fullscreen: false,
+    };
+    assert_eq!(geometry.to_string().parse(), Ok(geometry));
+    assert_eq!(
+        "# saved by the editor\n size = 800,600\nmonitor=0\nposition=10, 20\n\ntheme=dark\n".parse(),
+        Ok(WindowGeometry { monitor: 0, x: 10, y: 20, width: 800, height: 600, maximized: false, fullscreen: false }),
+    );
+    assert_eq!("monitor=0\nsize=800\n".parse::<WindowGeometry>(), Err(WindowGeometryParseError::InvalidValue { line: 2, key: "size" }));
+    assert_eq!("monitor=0\nsize\n".parse::<WindowGeometry>(), Err(WindowGeometryParseError::MalformedLine { line: 2 }));
+    assert_eq!("monitor=0\nsize=1,1\n".parse::<WindowGeometry>(), Err(WindowGeometryParseError::MissingKey("position")));
+
+    let primary = Monitor {
+        index: 0,
+        name: String::new(),
+        x: 0,
+        y: 0,
+        width: 1920,
+        height: 1080,
+        physical_width: 0,
+        physical_height: 0,
+        refresh_rate: 60,
+    };
+    let scale = Vector2 { x: 1.0, y: 1.0 };
+    assert_eq!(geometry.fit_to(&primary, scale), (0, 100, 1280, 720));
+    let huge = WindowGeometry { x: 5000, y: 5000, width: 4000, height: 900, ..geometry };
+    assert_eq!(huge.fit_to(&primary, scale), (0, 180, 1920, 900));
+}
+
+#[test]
+fn test_virtual_screen_viewport() {
+    assert_eq!(ScaleMode::Integer.viewport(320, 180, 1280, 720), Rectangle::new(0.0, 0.0, 1280.0, 720.0));
+    assert_eq!(ScaleMode::Integer.viewport(320, 180, 1000, 800), Rectangle::new(20.0, 130.0, 960.0, 540.0));
+    assert_eq!(ScaleMode::Integer.viewport(320, 180, 200, 100), Rectangle::new(-60.0, -40.0, 320.0, 180.0));
+    assert_eq!(ScaleMode::Fit.viewport(320, 180, 1000, 800), Rectangle::new(0.0, 118.0, 1000.0, 562.5));
+    assert_eq!(ScaleMode::Fit.viewport(100, 100, 300, 200), Rectangle::new(50.0, 0.0, 200.0, 200.0));
+    assert_eq!(ScaleMode::Stretch.viewport(320, 180, 1000, 800), Rectangle::new(0.0, 0.0, 1000.0, 800.0));
+}
+
+#[test]
+fn test_event_loop_mode() {
+    use std::time::{Duration, Instant};
+    let now = Instant::now();
+    let mut event_loop = EventLoop::new();
+    assert_eq!(event_loop.mode(now), LoopMode::Wait);
+
+    event_loop.wake_at(now + Duration::from_secs(2));
+    event_loop.wake_at(now + Duration::from_secs(1));
+    event_loop.wake_at(now + Duration::from_secs(3));
+    assert_eq!(event_loop.deadline(), Some(now + Duration::from_secs(1)), "the earliest deadline should be kept");
+    assert_eq!(event_loop.mode(now), LoopMode::Poll);
+    assert_eq!(event_loop.mode(now + Duration::from_secs(1)), LoopMode::Continuous);
+
+    event_loop.cancel_wake();
+    event_loop.request_redraw();
+    assert_eq!(event_loop.mode(now), LoopMode::Continuous);
+
+    let mut animating = EventLoop::new();
+    animating.set_continuous(true);
+    assert_eq!(animating.mode(now), LoopMode::Continuous);
+}
+
+#[test]
+fn test_game_loop_accumulator() {
+    use std::time::Duration;
+    let step = Duration::from_millis(10);
+    let mut game_loop = GameLoop::new(step).with_max_frame_time(Duration::from_millis(100));
+
+    let ticks = game_loop.advance(Duration::from_millis(25));
+    assert_eq!(ticks.updates, 2);
+    assert!((ticks.alpha - 0.5).abs() < 1e-4, "5ms of a 10ms step should be left over");
+
+    let ticks = game_loop.advance(Duration::from_millis(5));
+    assert_eq!(ticks.updates, 1, "leftover time should carry into the next frame");
+    assert!(ticks.alpha.abs() < 1e-4);
+
+    let ticks = game_loop.advance(Duration::from_secs(3));
+    assert_eq!(ticks.updates, 10, "a long pause should be clamped to the maximum frame time");
+    let stats = game_loop.stats();
+    assert_eq!(stats.frames, 3);
+    assert_eq!(stats.updates, 13);
+    assert_eq!(stats.frame_updates, 10);
+    assert_eq!(stats.clamped_frames, 1);
+    assert_eq!(stats.dropped_time, Duration::from_millis(2900));
+}
+
+#[test]
+fn test_input_state_by_hand() {
+    let mut input = InputState::default();
+    input.keys.down.push(KeyboardKey::LeftControl);
+    input.keys.pressed.push(KeyboardKey::S);
+    input.mouse.wheel = Vector2 { x: 0.5, y: -2.0 };
+    let mut pad = GamepadState::new(1);
+    pad.buttons.down.push(GamepadButton::RightFaceDown);
+    pad.set_axis(GamepadAxis::LeftX, -0.75);
+    input.gamepads.push(pad);
+
+    assert!(input.is_key_down(KeyboardKey::LeftControl) && input.is_key_pressed(KeyboardKey::S));
+    assert!(input.is_key_up(KeyboardKey::S), "pressed keys are only down if listed as held");
+    assert_eq!(input.get_mouse_wheel_move(), -2.0);
+    assert!(!input.is_gamepad_available(0));
+    assert!(input.is_gamepad_button_down(1, GamepadButton::RightFaceDown));
+    assert_eq!(input.get_gamepad_axis_movement(1, GamepadAxis::LeftX), -0.75);
+    assert_eq!(input.get_gamepad_axis_movement(0, GamepadAxis::LeftX), 0.0);
+}
+
+#[test]
+fn test_action_map_bindings() {
+    use std::f32::consts::FRAC_1_SQRT_2;
+    const JUMP: u8 = 0;
+    const MOVE: u8 = 1;
+    const SAVE: u8 = 2;
+    let mut actions = ActionMap::new().with_dead_zone(0.25);
+    actions.bind(JUMP, InputSource::Key(KeyboardKey::Space));
+    actions.bind(MOVE, Binding::wasd());
+    actions.bind(MOVE, Binding::Stick { x: GamepadAxis::LeftX, y: GamepadAxis::LeftY });
+    actions.bind(SAVE, Binding::Chord(vec![InputSource::Key(KeyboardKey::LeftControl), InputSource::Key(KeyboardKey::S)]));
+
+    let mut input = InputState::default();
+    input.keys.down.extend([KeyboardKey::W, KeyboardKey::D, KeyboardKey::LeftControl]);
+    let mut pad = GamepadState::new(0);
+    pad.set_axis(GamepadAxis::LeftX, 0.2);
+    input.gamepads.push(pad);
+    actions.update_from(&input);
+    let movement = actions.vector(MOVE);
+    assert!((movement.x - FRAC_1_SQRT_2).abs() < 1e-6 && (movement.y + FRAC_1_SQRT_2).abs() < 1e-6, "diagonals should be normalized");
+    assert!(!actions.is_down(SAVE) && !actions.is_down(JUMP));
+
+    input.keys.down.push(KeyboardKey::S);
+    actions.update_from(&input);
+    assert!(actions.is_pressed(SAVE));
+    actions.update_from(&input);
+    assert!(actions.is_down(SAVE) && !actions.is_pressed(SAVE));
+    input.keys.down.clear();
+    actions.update_from(&input);
+    assert!(actions.is_released(SAVE));
+    assert_eq!(actions.vector(MOVE), Vector2::default(), "a stick inside the dead zone should be ignored");
+
+    let text = actions.to_string();
+    assert_eq!(text, "0 = Space\n1 = vector(W, S, A, D)\n1 = stick(GamepadAxis.LeftX, GamepadAxis.LeftY)\n2 = LeftControl+S\n");
+    let mut loaded = ActionMap::<u8>::new();
+    loaded.load_bindings(&text).unwrap();
+    assert_eq!(loaded.all_bindings(), actions.all_bindings());
+    assert_eq!(
+        loaded.load_bindings("# comment\n0 = axis(Space)"),
+        Err(ActionMapParseError::InvalidBinding { line: 2, error: BindingParseError::InvalidArguments("axis".into()) }),
+    );
+    assert_eq!(loaded.all_bindings(), actions.all_bindings(), "bindings should be unchanged on error");
+    assert_eq!("-GamepadAxis.LeftY".parse(), Ok(InputSource::GamepadAxis(GamepadAxis::LeftY, AxisDirection::Negative)));
+}
+
+#[test]
+fn test_enum_names() {
+    assert_eq!(KeyboardKey::LeftControl.to_string(), "LeftControl");
+    assert_eq!(GamepadButton::RightTrigger2.to_string(), "Gamepad.RightTrigger2");
+    assert_eq!("Mouse.Right".parse(), Ok(MouseButton::Right));
+    assert_eq!("Right".parse(), Ok(KeyboardKey::Right));
+    assert_eq!("Right".parse::<MouseButton>(), Err(ParseEnumError { enum_name: "MouseButton" }));
+    for axis in GamepadAxis::ALL {
+        assert_eq!(axis.to_string().parse(), Ok(axis));
+        assert_eq!(GamepadAxis::try_from(axis as i32), Ok(axis));
+    }
+    assert_eq!(KeyboardKey::try_from(0), Err(()), "KEY_NULL is not a key");
+}
+
+#[test]
+fn test_text_input_editing() {
+    let mut input = TextInput::new().with_max_len(12);
+    assert!(input.insert("héllo"));
+    input.insert(" wörld");
+    assert_eq!(input.text(), "héllo wörld");
+    assert!(input.undo(), "consecutive typing should undo as one step");
+    assert_eq!(input.text(), "");
+    assert!(input.redo());
+    assert_eq!(input.caret(), input.text().len());
+
+    input.move_caret(Movement::WordLeft, true);
+    assert_eq!(input.selected_text(), "wörld");
+    assert_eq!(input.cut().as_deref(), Some("wörld"));
+    input.paste("there, and more");
+    assert_eq!(input.text(), "héllo there,", "pasting should stop at the length limit");
+    input.undo();
+    assert_eq!(input.text(), "héllo ");
+
+    // The caret moves over a letter with a combining accent and a flag as single characters
+    let mut input = TextInput::new().with_text("e\u{301}🇳🇱!");
+    input.move_caret(Movement::Left, false);
+    input.move_caret(Movement::Left, false);
+    assert_eq!(input.caret(), "e\u{301}".len());
+    input.delete(Movement::Left);
+    assert_eq!(input.text(), "🇳🇱!");
+
+    let mut digits = TextInput::new().with_filter(|c| c.is_ascii_digit());
+    assert!(!digits.insert("abc"));
+    digits.insert("a1b2\n3");
+    assert_eq!(digits.text(), "123");
+    digits.select_all();
+    digits.delete(Movement::Left);
+    assert_eq!(digits.text(), "");
+}
+
+#[test]
+fn test_gamepad_events() {
+    let pad = |index, name: &str| GamepadState { name: Some(name.to_owned()), ..GamepadState::new(index) };
+    let info = |index, name: &str| GamepadInfo { index, name: Some(name.to_owned()) };
+    let mut gamepads = Gamepads::new();
+    let mut input = InputState::default();
+    assert!(gamepads.update_from(&input).is_empty());
+
+    input.gamepads = vec![pad(0, "Xbox"), pad(2, "DualSense")];
+    assert_eq!(gamepads.update_from(&input), [
+        GamepadEvent::Connected(info(0, "Xbox")),
+        GamepadEvent::Connected(info(2, "DualSense")),
+    ]);
+    assert!(gamepads.update_from(&input).is_empty(), "events should only be reported once");
+    assert_eq!(gamepads.name(2), Some("DualSense"));
+
+    input.gamepads = vec![pad(0, "Switch Pro")];
+    assert_eq!(gamepads.update_from(&input), [
+        GamepadEvent::Disconnected(info(0, "Xbox")),
+        GamepadEvent::Connected(info(0, "Switch Pro")),
+        GamepadEvent::Disconnected(info(2, "DualSense")),
+    ]);
+    assert!(!gamepads.is_connected(2));
+    assert_eq!(gamepads.iter().map(|pad| pad.index).collect::<Vec<_>>(), [0]);
+}
+
+#[test]
+fn test_gamepad_mappings_parse() {
+    let db = b"# Game Controller DB\n\
+        \n\
+        03000000de280000ff11000001000000,Steam Virtual Gamepad,a:b0,b:b1,leftx:a0,platform:Linux,\r\n\
+        03000000de280000ff11000001000000,Steam Virtual Gamepad,a:b0,b:b1,leftx:a0,platform:Windows,\n\
+        030000005e0400008e02000010010000,Xbox 360 Controller,a:b0,b:b1,\n\
+        not-a-guid,Broken,a:b0,platform:Linux,\n\
+        030000005e0400008e02000010010000,,a:b0,platform:Linux,\n\
+        030000005e0400008e02000010010000,Typo,a=b0,platform:Linux,\n\
+        030000005e0400008e02000010010000,Bad \xff bytes,a:b0,platform:Linux,";
+    let mappings = GamepadMappings::parse_for(db, Some("Linux"));
+    assert_eq!(mappings.iter().map(|(line, _)| line).collect::<Vec<_>>(), [3, 5], "platform-less mappings apply everywhere");
+    assert!(mappings.iter().next().unwrap().1.ends_with("platform:Linux,"), "trailing whitespace should be trimmed");
+    assert_eq!(mappings.other_platforms(), 1);
+    assert_eq!(mappings.errors(), [
+        GamepadMappingError::InvalidGuid { line: 6 },
+        GamepadMappingError::MissingName { line: 7 },
+        GamepadMappingError::InvalidField { line: 8, field: "a=b0".to_owned() },
+        GamepadMappingError::InvalidUtf8 { line: 9 },
+    ]);
+    assert_eq!(mappings.errors()[2].to_string(), "line 8: expected `key:value`, found `a=b0`");
+    assert_eq!(GamepadMappings::parse_for(db, Some("Windows")).len(), 2);
+}
+
+#[test]
+fn test_rumble_patterns() {
+    use std::time::Duration;
+    let ms = Duration::from_millis;
+    let pattern = RumblePattern::new()
+        .keyframe(ms(100), Rumble::new(0.0, 1.0), RumbleCurve::Step)
+        .keyframe(ms(200), Rumble::new(1.0, 0.0), RumbleCurve::Linear)
+        .keyframe(ms(300), Rumble::new(0.0, 0.0), RumbleCurve::Step);
+    assert_eq!(pattern.sample(ms(50)), Rumble::new(0.0, 1.0), "the first keyframe should hold until it is reached");
+    assert_eq!(pattern.sample(ms(150)), Rumble::new(0.5, 0.5));
+    assert_eq!(pattern.sample(ms(299)), Rumble::new(1.0, 0.0), "steps should hold until the keyframe");
+    assert!(pattern.sample(ms(300)).is_off() && pattern.is_finished(ms(300)));
+    assert_eq!(RumbleCurve::EaseIn.apply(0.5), 0.25);
+    assert_eq!(RumbleCurve::EaseOut.apply(0.5), 0.75);
+
+    let heartbeat = RumblePattern::heartbeat();
+    assert_eq!(heartbeat.sample(ms(2060)), heartbeat.sample(ms(60)), "looping patterns should start over");
+    assert!(!heartbeat.is_finished(Duration::from_secs(60)));
+
+    let mut player = RumblePlayer::new(RumbleBlend::Max);
+    player.play(RumblePattern::hit(0.5));
+    player.play_scaled(pattern.clone(), 0.5);
+    assert_eq!(player.advance(ms(150)).right, 0.25);
+    assert_eq!(player.len(), 2);
+    assert_eq!(player.advance(ms(50)), Rumble::new(0.5, 0.0), "the hit should have finished");
+    assert_eq!(player.len(), 1);
+
+    let mut player = RumblePlayer::new(RumbleBlend::Sum);
+    player.play(pattern.clone());
+    player.play(pattern);
+    assert_eq!(player.advance(ms(150)), Rumble::new(1.0, 1.0));
+    assert_eq!(player.advance(ms(50)), Rumble::new(1.0, 0.0), "sums should be clamped");
+    player.advance(ms(100));
+    assert!(!player.is_playing() && player.current().is_off());
+}
+
+#[test]
+fn test_gesture_state() {
+    use std::time::Duration;
+    use crate::low::types::Gesture;
+    let mut gestures = GestureState::default();
+    assert!(!gestures.tap() && gestures.hold().is_none() && gestures.swipe().is_none() && gestures.pinch().is_none());
+
+    gestures.detected = Gesture::Hold;
+    gestures.hold_duration = Duration::from_millis(800);
+    assert_eq!(gestures.hold(), Some(Duration::from_millis(800)));
+    assert!(gestures.drag().is_none());
+
+    gestures.detected = Gesture::SwipeUp;
+    gestures.drag_angle = 95.0;
+    assert_eq!(gestures.swipe(), Some(Swipe { direction: SwipeDirection::Up, angle: 95.0 }));
+
+    gestures.detected = Gesture::PinchOut;
+    gestures.pinch_vector = Vector2 { x: 3.0, y: 4.0 };
+    let pinch = gestures.pinch().unwrap();
+    assert!(pinch.outward && pinch.vector == gestures.pinch_vector);
+    assert!(gestures.is_detected(Gesture::PinchIn | Gesture::PinchOut));
+
+    let points = (0..10).map(|id| TouchPoint { id, position: Vector2 { x: id as f32, y: 0.0 } });
+    let event = GestureEvent::new(TouchAction::Move, points).as_sys();
+    assert_eq!(event.pointCount, GestureEvent::MAX_POINTS as i32, "extra touch points should be dropped");
+    assert_eq!(event.touchAction, TouchAction::Move as i32);
+    assert_eq!(event.position[7].x, 7.0);
+}
+
+#[test]
+fn test_touch_tracker_replay() {
+    use std::time::Duration;
+    use TouchPhase::*;
+    let frame = Duration::from_millis(100);
+    let point = |id, x, y| TouchPoint { id, position: Vector2 { x, y } };
+    // Fingers 7 and 9 go down, 7 drags right and lifts, so 9 moves to the first slot
+    let frames = [
+        vec![point(7, 10.0, 10.0)],
+        vec![point(7, 10.0, 10.0), point(9, 50.0, 50.0)],
+        vec![point(7, 30.0, 10.0), point(9, 50.0, 50.0)],
+        vec![point(9, 50.0, 40.0)],
+        vec![point(9, 50.0, 40.0)],
+        vec![],
+    ];
+    let mut tracker = TouchTracker::new();
+    let mut phases = Vec::new();
+    for touches in frames {
+        tracker.update_from(&InputState { touches, ..Default::default() }, frame);
+        phases.push(tracker.touches().iter().map(|touch| (touch.id, touch.phase)).collect::<Vec<_>>());
+        if let Some(touch) = tracker.get(7).filter(|touch| touch.phase == TouchPhase::Ended) {
+            assert_eq!(touch.total_delta(), Vector2 { x: 20.0, y: 0.0 });
+            assert_eq!(touch.velocity, Vector2 { x: 200.0, y: 0.0 }, "velocity should be kept for flicks");
+            assert_eq!(touch.duration, frame * 3);
+        }
+    }
+    assert_eq!(phases, [
+        vec![(7, Began)],
+        vec![(7, Stationary), (9, Began)],
+        vec![(7, Moved), (9, Stationary)],
+        vec![(7, Ended), (9, Moved)],
+        vec![(9, Stationary)],
+        vec![(9, Ended)],
+    ]);
+    tracker.update_from(&InputState::default(), frame);
+    assert!(tracker.touches().is_empty());
+
+    tracker.update_points([point(1, 0.0, 0.0), point(1, 5.0, 5.0)], frame);
+    assert_eq!(tracker.active_count(), 1, "duplicate ids should be ignored");
+    assert_eq!(tracker.get(1).unwrap().position, Vector2::default());
+}
+
+#[test]
+fn test_input_player_frames() {
+    let event = |frame, kind| AutomationEvent { frame, kind, params: [0; 4] };
+    let mut events: AutomationEvents = [
+        event(3, AutomationEventKind::KeyUp),
+        event(0, AutomationEventKind::KeyDown),
+        event(0, AutomationEventKind::WindowResize),
+    ].into_iter().collect();
+    events.push(event(1, AutomationEventKind::MousePosition));
+    assert_eq!(events.iter().map(|e| e.frame).collect::<Vec<_>>(), [0, 0, 1, 3], "events should be ordered by frame");
+    assert_eq!(events.frame_count(), 4);
+    events.retain(|e| e.kind.is_input());
+    assert_eq!(events.len(), 3);
+    assert_eq!(events.of_kind(AutomationEventKind::KeyUp).count(), 1);
+    assert_eq!(AutomationEventKind::try_from(AutomationEventKind::SetTargetFps as u32), Ok(AutomationEventKind::SetTargetFps));
+    assert_eq!(AutomationEventKind::try_from(0), Err(()));
+
+    let mut player = InputPlayer::new(events);
+    let due: Vec<usize> = (0..5).map(|_| player.advance().len()).collect();
+    assert_eq!(due, [1, 1, 0, 1, 0]);
+    assert!(player.is_finished());
+    player.restart();
+    assert_eq!(player.advance(), &[event(0, AutomationEventKind::KeyDown)]);
+}
+
+#[test]
+fn test_fs_paths_repeatable() {
+    use std::path::{Path, PathBuf};
+    let handles: Vec<_> = (0..4).map(|_| std::thread::spawn(|| {
+        for _ in 0..100 {
+            assert_eq!(get_directory_path(Path::new("/games/forest/level.tmx")), Some(PathBuf::from("/games/forest")));
+            assert_eq!(get_file_name_without_ext(Path::new("/games/forest/level.tmx")).as_deref(), Some("level".as_ref()));
+        }
+    })).collect();
+    for handle in handles {
+        handle.join().unwrap();
+    }
+    assert_eq!(get_prev_directory_path(Path::new("/games/forest")), Some(PathBuf::from("/games")));
+    assert_eq!(get_working_directory(), std::env::current_dir().ok());
+    assert_eq!(get_working_directory(), get_working_directory(), "should be callable more than once");
+    assert!(get_application_directory().is_some());
+}